@@ -37,7 +37,12 @@ from .clifford_gate import is_clifford
 from .gate import ParametricQuantumGate, QuantumGate
 from .gates import (
     CNOT,
+    CRX,
+    CRY,
+    CRZ,
     CZ,
+    ECR,
+    ISWAP,
     RX,
     RY,
     RZ,
@@ -46,6 +51,7 @@ from .gates import (
     U1,
     U2,
     U3,
+    GlobalPhase,
     H,
     Identity,
     Measurement,
@@ -55,9 +61,13 @@ from .gates import (
     ParametricRZ,
     Pauli,
     PauliRotation,
+    RX_deg,
+    RY_deg,
+    RZ_deg,
     S,
     Sdag,
     SingleQubitUnitaryMatrix,
+    SqrtISWAP,
     SqrtX,
     SqrtXdag,
     SqrtY,
@@ -107,15 +117,25 @@ __all__ = [
     "RX",
     "RY",
     "RZ",
+    "RX_deg",
+    "RY_deg",
+    "RZ_deg",
     "U1",
     "U2",
     "U3",
+    "GlobalPhase",
     "UnitaryMatrix",
     "SingleQubitUnitaryMatrix",
     "TwoQubitUnitaryMatrix",
     "CNOT",
     "CZ",
+    "CRX",
+    "CRY",
+    "CRZ",
     "SWAP",
+    "ISWAP",
+    "SqrtISWAP",
+    "ECR",
     "TOFFOLI",
     "Pauli",
     "PauliRotation",
@@ -19,9 +19,50 @@
 #: methods. A QuantumGate object contains information of gate name,
 #: control qubit, target qubit, classical bits, parameters, and pauli
 #: ids.
-from quri_parts.rust.circuit.gate import ParametricQuantumGate, QuantumGate
+from quri_parts.rust.circuit.gate import (
+    IndexConflictError,
+    InvalidUnitaryError,
+    ParametricQuantumGate,
+    QuantumGate,
+    UnknownGateError,
+    apply_gate,
+    count_by_name,
+    fuse_single_qubit,
+    gate_diff,
+    gates_from_arrays,
+    inverse_sequence,
+    is_parametric,
+    matrices,
+    merge_rotations,
+    supported_gates,
+    t_count,
+    tensor,
+    two_qubit_count,
+    u1,
+    u2,
+    u3,
+)
 
 __all__ = [
+    "IndexConflictError",
+    "InvalidUnitaryError",
     "ParametricQuantumGate",
     "QuantumGate",
+    "UnknownGateError",
+    "apply_gate",
+    "count_by_name",
+    "fuse_single_qubit",
+    "gate_diff",
+    "gates_from_arrays",
+    "inverse_sequence",
+    "is_parametric",
+    "matrices",
+    "merge_rotations",
+    "supported_gates",
+    "t_count",
+    "tensor",
+    "two_qubit_count",
+    "u1",
+    "u2",
+    "u3",
 ]
@@ -109,6 +109,58 @@ def is_three_qubit_gate_name(gate_name: str) -> TypeGuard[ThreeQubitGateNameType
     return gate_name in THREE_QUBIT_GATE_NAMES
 
 
+ControlledRotationGateNameType: TypeAlias = Literal[
+    "CRX",
+    "CRY",
+    "CRZ",
+]
+
+CRX: Literal["CRX"] = "CRX"
+CRY: Literal["CRY"] = "CRY"
+CRZ: Literal["CRZ"] = "CRZ"
+
+CONTROLLED_ROTATION_GATE_NAMES: set[ControlledRotationGateNameType] = {
+    CRX,
+    CRY,
+    CRZ,
+}
+
+
+def is_controlled_rotation_gate_name(
+    gate_name: str,
+) -> TypeGuard[ControlledRotationGateNameType]:
+    return gate_name in CONTROLLED_ROTATION_GATE_NAMES
+
+
+ISwapGateNameType: TypeAlias = Literal[
+    "ISWAP",
+    "SqrtISWAP",
+]
+
+ISWAP: Literal["ISWAP"] = "ISWAP"
+SqrtISWAP: Literal["SqrtISWAP"] = "SqrtISWAP"
+
+ISWAP_GATE_NAMES: set[ISwapGateNameType] = {
+    ISWAP,
+    SqrtISWAP,
+}
+
+
+def is_iswap_gate_name(gate_name: str) -> TypeGuard[ISwapGateNameType]:
+    return gate_name in ISWAP_GATE_NAMES
+
+
+GlobalPhaseGateNameType: TypeAlias = Literal["GlobalPhase"]
+
+GlobalPhase: Literal["GlobalPhase"] = "GlobalPhase"
+
+GLOBAL_PHASE_GATE_NAMES: set[GlobalPhaseGateNameType] = {GlobalPhase}
+
+
+def is_global_phase_gate_name(gate_name: str) -> TypeGuard[GlobalPhaseGateNameType]:
+    return gate_name in GLOBAL_PHASE_GATE_NAMES
+
+
 MultiQubitGateNameType: TypeAlias = Literal[
     "Pauli",
     "PauliRotation",
@@ -243,6 +295,9 @@ GateNameType: TypeAlias = Literal[
     SingleQubitGateNameType,
     TwoQubitGateNameType,
     ThreeQubitGateNameType,
+    ControlledRotationGateNameType,
+    ISwapGateNameType,
+    GlobalPhaseGateNameType,
     MultiQubitGateNameType,
     UnitaryMatrixGateNameType,
     ParametricGateNameType,
@@ -253,6 +308,9 @@ GATE_NAMES: set[GateNameType] = (
     SINGLE_QUBIT_GATE_NAMES
     | TWO_QUBIT_GATE_NAMES
     | THREE_QUBIT_GATE_NAMES
+    | CONTROLLED_ROTATION_GATE_NAMES
+    | ISWAP_GATE_NAMES
+    | GLOBAL_PHASE_GATE_NAMES
     | MULTI_QUBIT_GATE_NAMES
     | UNITARY_MATRIX_GATE_NAMES
     | PARAMETRIC_GATE_NAMES
@@ -15,7 +15,12 @@ from typing_extensions import deprecated
 from quri_parts.circuit import gate_names
 from quri_parts.rust.circuit.gates import (
     CNOT,
+    CRX,
+    CRY,
+    CRZ,
     CZ,
+    ECR,
+    ISWAP,
     RX,
     RY,
     RZ,
@@ -24,6 +29,7 @@ from quri_parts.rust.circuit.gates import (
     U1,
     U2,
     U3,
+    GlobalPhase,
     H,
     Identity,
     Measurement,
@@ -33,9 +39,13 @@ from quri_parts.rust.circuit.gates import (
     ParametricRZ,
     Pauli,
     PauliRotation,
+    RX_deg,
+    RY_deg,
+    RZ_deg,
     S,
     Sdag,
     SingleQubitUnitaryMatrix,
+    SqrtISWAP,
     SqrtX,
     SqrtXdag,
     SqrtY,
@@ -96,6 +106,15 @@ RY = RY
 #: RZ gate equivalant to :math:`\exp(-i\theta Z/2)` represented by matrix
 #: :math:`\begin{pmatrix} e^{-i\theta/2} & 0 \\ 0 & e^{i\theta/2} \end{pmatrix}`
 RZ = RZ
+
+#: RX gate with the angle given in degrees. The angle is stored in radians.
+RX_deg = RX_deg
+
+#: RY gate with the angle given in degrees. The angle is stored in radians.
+RY_deg = RY_deg
+
+#: RZ gate with the angle given in degrees. The angle is stored in radians.
+RZ_deg = RZ_deg
 #: U1 gate is a single-qubit rotation about the Z axis:
 #:
 #: :math:`U_1(\lambda) = e^{i\lambda/2} R_Z(\lambda)`.
@@ -114,14 +133,37 @@ U2 = U2
 #: e^{i\phi}\sin\frac{\theta}{2} & e^{i(\phi+\lambda)}\cos\frac{\theta}{2}
 #: \end{pmatrix}`
 U3 = U3
+#: GlobalPhase gate multiplies the state by :math:`e^{i\phi}`. It is attached to
+#: a target qubit so that it can be controlled, which turns the phase into a
+#: relative phase :math:`U_1(\phi)` on the control qubit.
+#: Represented by matrix :math:`e^{i\phi}I`
+GlobalPhase = GlobalPhase
 #: CNOT gate, also called controlled-X gate, or CX gate CNOT(control, target) =
 #: :math:`I \otimes |0\rangle\langle0| + X \otimes |1\rangle\langle1|`
 CNOT = CNOT
 #: CZ gate, also called controlled-Z gate CZ(control, target) =
 #: :math:`I \otimes |0\rangle\langle0| + Z \otimes |1\rangle\langle1|`
 CZ = CZ
+#: Controlled RX gate CRX(control, target, angle) =
+#: :math:`I \otimes |0\rangle\langle0| + R_X(\theta) \otimes |1\rangle\langle1|`
+CRX = CRX
+#: Controlled RY gate CRY(control, target, angle) =
+#: :math:`I \otimes |0\rangle\langle0| + R_Y(\theta) \otimes |1\rangle\langle1|`
+CRY = CRY
+#: Controlled RZ gate CRZ(control, target, angle) =
+#: :math:`I \otimes |0\rangle\langle0| + R_Z(\theta) \otimes |1\rangle\langle1|`
+CRZ = CRZ
 #: SWAP gate.
 SWAP = SWAP
+#: iSWAP gate, which swaps the qubits and adds a phase :math:`i` to
+#: :math:`|01\rangle` and :math:`|10\rangle`.
+ISWAP = ISWAP
+#: Square root of the iSWAP gate.
+SqrtISWAP = SqrtISWAP
+#: Echoed cross-resonance gate ECR(target1, target2) =
+#: :math:`(IX - XY) / \sqrt{2}` with target1 as the right factor, a native
+#: two-qubit gate of IBM hardware.
+ECR = ECR
 #: TOFFOLI gate.
 TOFFOLI = TOFFOLI
 #: UnitaryMatrix gate represented by an arbitrary unitary matrix.
@@ -488,12 +530,22 @@ __all__ = [
     "SingleQubitUnitaryMatrix",
     "UnitaryMatrix",
     "TOFFOLI",
+    "ECR",
+    "SqrtISWAP",
+    "ISWAP",
     "SWAP",
+    "CRZ",
+    "CRY",
+    "CRX",
     "CZ",
     "CNOT",
+    "GlobalPhase",
     "U3",
     "U2",
     "U1",
+    "RZ_deg",
+    "RY_deg",
+    "RX_deg",
     "RZ",
     "RY",
     "RX",
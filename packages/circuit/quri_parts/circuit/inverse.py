@@ -21,7 +21,14 @@ from quri_parts.circuit import (
 )
 
 from .gate import QuantumGate
-from .gate_names import SingleQubitGateNameType, is_single_qubit_gate_name
+from .gate_names import (
+    ControlledRotationGateNameType,
+    SingleQubitGateNameType,
+    is_controlled_rotation_gate_name,
+    is_global_phase_gate_name,
+    is_iswap_gate_name,
+    is_single_qubit_gate_name,
+)
 
 _single_qubit_gate_dagger: Mapping[
     SingleQubitGateNameType, Callable[[int], QuantumGate]
@@ -52,6 +59,14 @@ _rotation_gate_dagger: Mapping[
     gate_names.U3: gates.U3,
 }
 
+_controlled_rotation_gate_dagger: Mapping[
+    ControlledRotationGateNameType, Callable[[int, int, float], QuantumGate]
+] = {
+    gate_names.CRX: gates.CRX,
+    gate_names.CRY: gates.CRY,
+    gate_names.CRZ: gates.CRZ,
+}
+
 
 def inverse_gate(gate: QuantumGate) -> QuantumGate:
     target_indices = gate.target_indices
@@ -64,6 +79,15 @@ def inverse_gate(gate: QuantumGate) -> QuantumGate:
             inverse_gate = _rotation_gate_dagger[gate.name](*target_indices, *inv_param)
         else:
             inverse_gate = gate
+    elif is_controlled_rotation_gate_name(gate.name):
+        inverse_gate = _controlled_rotation_gate_dagger[gate.name](
+            *gate.control_indices, *target_indices, -gate.params[0]
+        )
+    elif is_iswap_gate_name(gate.name):
+        # The inverses are not named gates, so they become UnitaryMatrix gates.
+        inverse_gate = gate.inverse()
+    elif is_global_phase_gate_name(gate.name):
+        inverse_gate = gates.GlobalPhase(*target_indices, -gate.params[0])
     elif gate.name == gate_names.PauliRotation:
         pauli_ids = gate.pauli_ids
         angle = gate.params[0]
@@ -297,3 +297,1582 @@ def test_gate_equal() -> None:
     assert ParametricPauliRotation((0, 1, 2), (2, 1, 3)) == ParametricPauliRotation(
         (0, 2, 1), (2, 3, 1)
     )
+
+
+def test_gate_inverse() -> None:
+    assert X(0).inverse() == X(0)
+    assert H(1).inverse() == H(1)
+    assert CNOT(0, 1).inverse() == CNOT(0, 1)
+    assert S(0).inverse() == Sdag(0)
+    assert Sdag(0).inverse() == S(0)
+    assert T(0).inverse() == Tdag(0)
+    assert Tdag(0).inverse() == T(0)
+    assert RX(0, 0.1).inverse() == RX(0, -0.1)
+    assert RY(0, 0.1).inverse() == RY(0, -0.1)
+    assert RZ(0, 0.1).inverse() == RZ(0, -0.1)
+    assert PauliRotation((0, 1), (1, 3), 0.1).inverse() == PauliRotation(
+        (0, 1), (1, 3), -0.1
+    )
+    assert UnitaryMatrix((0,), ((1, 0), (0, 1j))).inverse() == UnitaryMatrix(
+        (0,), ((1, 0), (0, -1j))
+    )
+    with pytest.raises(ValueError):
+        Measurement([0], [0]).inverse()
+
+
+def test_gate_matrix() -> None:
+    assert np.allclose(X(0).matrix(), [[0, 1], [1, 0]])
+    assert np.allclose(RZ(0, 0.2).matrix(), np.diag([np.exp(-0.1j), np.exp(0.1j)]))
+    # The control qubit corresponds to the lowest bit.
+    assert np.allclose(
+        CNOT(0, 1).matrix(),
+        [[1, 0, 0, 0], [0, 0, 0, 1], [0, 0, 1, 0], [0, 1, 0, 0]],
+    )
+    umat = ((1, 0), (0, 1j))
+    assert np.allclose(SingleQubitUnitaryMatrix(0, umat).matrix(), umat)
+    with pytest.raises(ValueError):
+        Measurement([0], [0]).matrix()
+
+
+def test_parametric_gate_hash() -> None:
+    a = ParametricQuantumGate("ParametricRX", target_indices=(0,), control_indices=(1,))
+    b = ParametricQuantumGate("ParametricRX", target_indices=(0,), control_indices=(2,))
+    assert a != b
+    assert hash(a) != hash(b)
+    assert hash(a) == hash(
+        ParametricQuantumGate("ParametricRX", target_indices=(0,), control_indices=(1,))
+    )
+    assert hash(ParametricPauliRotation((0, 1, 2), (2, 1, 3))) == hash(
+        ParametricPauliRotation((0, 2, 1), (2, 3, 1))
+    )
+
+
+def test_gate_isclose() -> None:
+    assert RX(0, 0.1).isclose(RX(0, 0.1 + 1e-13))
+    assert not RX(0, 0.1).isclose(RX(0, 0.1 + 1e-6))
+    assert RX(0, 0.1).isclose(RX(0, 0.1 + 1e-6), atol=1e-5)
+    assert not RX(0, 0.1).isclose(RY(0, 0.1))
+    assert not RX(0, 0.1).isclose(RX(1, 0.1))
+    assert UnitaryMatrix((0,), ((1, 0), (0, 1j))).isclose(
+        UnitaryMatrix((0,), ((1, 0), (0, 1j + 1e-13)))
+    )
+
+
+def test_unitary_matrix_shape_validation() -> None:
+    with pytest.raises(ValueError):
+        QuantumGate(
+            gate_names.UnitaryMatrix,
+            target_indices=(0, 1),
+            unitary_matrix=((1, 0, 0), (0, 1, 0), (0, 0, 1)),
+        )
+    with pytest.raises(ValueError):
+        QuantumGate(
+            gate_names.UnitaryMatrix,
+            target_indices=(0,),
+            unitary_matrix=((1, 0), (0,)),
+        )
+    with pytest.raises(ValueError):
+        QuantumGate("Custom", target_indices=(0,), unitary_matrix=((1, 0, 0, 0),))
+
+
+def test_gate_factory_index_validation() -> None:
+    with pytest.raises(ValueError):
+        CNOT(0, 0)
+    with pytest.raises(ValueError):
+        CZ(1, 1)
+    with pytest.raises(ValueError):
+        SWAP(2, 2)
+    with pytest.raises(ValueError):
+        TOFFOLI(0, 1, 0)
+    with pytest.raises(ValueError):
+        QuantumCircuit(2).add_CNOT_gate(1, 1)
+
+
+def test_gate_controlled() -> None:
+    assert X(1).controlled(0) == CNOT(0, 1)
+    assert Z(1).controlled(0) == CZ(0, 1)
+    assert CNOT(1, 2).controlled(0) == TOFFOLI(0, 1, 2)
+    assert H(1).controlled(0) == QuantumGate(
+        gate_names.H, target_indices=(1,), control_indices=(0,)
+    )
+    assert CZ(1, 2).controlled(0) == QuantumGate(
+        gate_names.Z, target_indices=(2,), control_indices=(0, 1)
+    )
+    controlled_u1 = U1(1, 0.5).controlled(0)
+    assert controlled_u1.control_indices == (0,)
+    assert controlled_u1.params == (0.5,)
+    with pytest.raises(ValueError):
+        X(0).controlled(0)
+    with pytest.raises(ValueError):
+        Measurement([0], [0]).controlled(1)
+    with pytest.raises(ValueError):
+        TOFFOLI(1, 2, 3).controlled(0)
+    with pytest.raises(ValueError):
+        SWAP(1, 2).controlled(0)
+
+
+def test_gate_to_qasm() -> None:
+    assert H(2).to_qasm() == "h q[2];"
+    assert RX(0, 0.5).to_qasm() == "rx(0.5) q[0];"
+    assert U2(1, 0.5, 1.0).to_qasm() == "u2(0.5, 1.0) q[1];"
+    assert CNOT(0, 1).to_qasm() == "cx q[0], q[1];"
+    assert TOFFOLI(0, 1, 2).to_qasm() == "ccx q[0], q[1], q[2];"
+    assert Measurement([0, 1], [1, 0]).to_qasm() == (
+        "measure q[0] -> c[1];\nmeasure q[1] -> c[0];"
+    )
+    with pytest.raises(ValueError):
+        UnitaryMatrix((0,), ((1, 0), (0, 1))).to_qasm()
+
+
+def test_gate_from_qasm() -> None:
+    assert QuantumGate.from_qasm("h q[2];") == H(2)
+    assert QuantumGate.from_qasm("  rx( 0.5 )  q [0] ;") == RX(0, 0.5)
+    assert QuantumGate.from_qasm("u3(pi/2, -pi, 0.25) q[1];") == U3(
+        1, np.pi / 2, -np.pi, 0.25
+    )
+    assert QuantumGate.from_qasm("cx q[0],q[1];") == CNOT(0, 1)
+    assert QuantumGate.from_qasm("measure q[0] -> c[1];") == Measurement([0], [1])
+    for gate in [S(0), U2(1, 0.5, 1.0), TOFFOLI(0, 1, 2), RZ(0, 1e-10)]:
+        assert QuantumGate.from_qasm(gate.to_qasm()) == gate
+    with pytest.raises(ValueError):
+        QuantumGate.from_qasm("foo q[0];")
+    with pytest.raises(ValueError):
+        QuantumGate.from_qasm("h q0;")
+    with pytest.raises(ValueError):
+        QuantumGate.from_qasm("rx q[0];")
+    for line in [
+        "rx() q[0];",
+        "u2(0.1,) q[0];",
+        "rx(nan) q[0];",
+        "rx(inf) q[0];",
+        "rx(1/0) q[0];",
+    ]:
+        with pytest.raises(ValueError):
+            QuantumGate.from_qasm(line)
+
+
+def test_gate_json() -> None:
+    gates = [
+        H(0),
+        RX(1, 0.1),
+        U3(0, 1.0 / 3.0, -1e-300, 2.5),
+        TOFFOLI(0, 1, 2),
+        PauliRotation((0, 2), (1, 3), 0.7),
+        Measurement([0, 1], [1, 0]),
+        UnitaryMatrix((0,), ((0.5 + 0.5j, 0.5 - 0.5j), (0.5 - 0.5j, 0.5 + 0.5j))),
+    ]
+    for gate in gates:
+        assert QuantumGate.from_json(gate.to_json()) == gate
+    assert QuantumGate.from_json('{"name": "X", "target_indices": [3]}') == X(3)
+    with pytest.raises(ValueError):
+        QuantumGate.from_json('{"name": "X"}')
+    with pytest.raises(ValueError):
+        QuantumGate.from_json('{"name": "X", "target_indices": [-1]}')
+    with pytest.raises(ValueError):
+        RX(0, float("nan")).to_json()
+
+
+def test_gate_qubit_count() -> None:
+    assert X(3).qubit_count == 1
+    assert CNOT(0, 1).qubit_count == 2
+    assert TOFFOLI(0, 1, 2).qubit_count == 3
+    assert Pauli((0, 2, 5), (1, 2, 3)).qubit_count == 3
+    assert Measurement([0, 1], [2, 3]).qubit_count == 2
+
+
+def test_gate_matrix_array() -> None:
+    mat = ((0, 1j), (1j, 0))
+    array = UnitaryMatrix((0,), mat).matrix_array
+    assert isinstance(array, np.ndarray)
+    assert array.dtype == np.complex128
+    assert np.array_equal(array, np.array(mat))
+    assert X(0).matrix_array is None
+
+
+def test_gate_structurally_equal() -> None:
+    rx = RX(0, 0.5)
+    rx_shifted = RX(0, 0.5 + 1e-12)
+    assert rx != rx_shifted
+    assert rx.structurally_equal(rx_shifted)
+    assert rx.isclose(rx_shifted)
+    assert not rx.structurally_equal(RX(1, 0.5))
+    assert not rx.structurally_equal(RY(0, 0.5))
+    assert CNOT(0, 1).structurally_equal(CNOT(0, 1))
+    assert not CNOT(0, 1).structurally_equal(CNOT(1, 0))
+    assert UnitaryMatrix((0,), ((1, 0), (0, 1))).structurally_equal(
+        UnitaryMatrix((0,), ((0, 1), (1, 0)))
+    )
+
+
+def test_gate_decompose() -> None:
+    gates = TOFFOLI(0, 1, 2).decompose()
+    assert len(gates) == 15
+    assert all(len(g.target_indices) + len(g.control_indices) <= 2 for g in gates)
+    assert H(0).decompose() == [H(0)]
+    assert CNOT(0, 1).decompose() == [CNOT(0, 1)]
+    ccz = QuantumGate(gate_names.Z, target_indices=(2,), control_indices=(0, 1))
+    ccz_gates = ccz.decompose()
+    assert {g.name for g in ccz_gates} == {gate_names.CNOT, gate_names.U1}
+    assert sum(g.name == gate_names.U1 for g in ccz_gates) == 7
+
+
+def test_gate_remap_qubits() -> None:
+    assert CNOT(0, 1).remap_qubits({0: 3, 1: 2}) == CNOT(3, 2)
+    assert TOFFOLI(0, 1, 2).remap_qubits({2: 5}) == TOFFOLI(0, 1, 5)
+    assert Measurement([0, 1], [0, 1]).remap_qubits(
+        {0: 1, 1: 0}, classical_mapping={0: 2}
+    ) == Measurement([1, 0], [2, 1])
+    with pytest.raises(ValueError):
+        TOFFOLI(0, 1, 2).remap_qubits({2: 5}, strict=True)
+    with pytest.raises(ValueError):
+        CNOT(0, 1).remap_qubits({0: 1})
+
+
+def test_gate_kind() -> None:
+    expected = {
+        "Pauli": [
+            Identity(0),
+            X(0),
+            Y(0),
+            Z(0),
+            Pauli((0, 1), (1, 2)),
+        ],
+        "Clifford": [
+            H(0),
+            S(0),
+            Sdag(0),
+            SqrtX(0),
+            SqrtXdag(0),
+            SqrtY(0),
+            SqrtYdag(0),
+            CNOT(0, 1),
+            CZ(0, 1),
+            SWAP(0, 1),
+        ],
+        "NonClifford": [T(0), Tdag(0), TOFFOLI(0, 1, 2)],
+        "Rotation": [
+            RX(0, 0.1),
+            RY(0, 0.1),
+            RZ(0, 0.1),
+            U1(0, 0.1),
+            U2(0, 0.1, 0.2),
+            U3(0, 0.1, 0.2, 0.3),
+            PauliRotation((0, 1), (1, 2), 0.1),
+        ],
+        "UnitaryMatrix": [UnitaryMatrix((0,), ((1, 0), (0, 1)))],
+        "Measurement": [Measurement([0], [0])],
+    }
+    for kind, gates in expected.items():
+        for gate in gates:
+            assert gate.kind == kind, gate.name
+    for parametric_gate in [
+        ParametricRX(0),
+        ParametricRY(0),
+        ParametricRZ(0),
+        ParametricPauliRotation((0,), (1,)),
+    ]:
+        assert parametric_gate.kind == "Parametric"
+
+
+def test_gate_is_clifford() -> None:
+    for gate in [H(0), S(0), Sdag(0), X(0), Y(0), Z(0), CNOT(0, 1), CZ(0, 1), SWAP(0, 1)]:
+        assert gate.is_clifford()
+    for gate in [T(0), Tdag(0), TOFFOLI(0, 1, 2), Measurement([0], [0])]:
+        assert not gate.is_clifford()
+    assert RX(0, np.pi / 2).is_clifford()
+    assert RZ(0, -3 * np.pi).is_clifford()
+    assert not RY(0, 0.1).is_clifford()
+    assert PauliRotation((0, 1), (1, 3), np.pi).is_clifford()
+    assert U2(0, 0.0, np.pi).is_clifford()
+    assert not U3(0, 0.1, 0.0, 0.0).is_clifford()
+    s = np.sqrt(0.5)
+    assert UnitaryMatrix((0,), ((s, s), (s, -s))).is_clifford()
+    assert not UnitaryMatrix((0,), ((1, 0), (0, np.exp(0.25j * np.pi)))).is_clifford()
+
+
+def test_gate_propagate_pauli() -> None:
+    assert H(0).propagate_pauli([1], [0]) == ([0], [3], 1)
+    assert S(0).propagate_pauli([2], [0]) == ([0], [1], -1)
+    assert X(0).propagate_pauli([3], [0]) == ([0], [3], -1)
+    assert CNOT(0, 1).propagate_pauli([1], [0]) == ([0, 1], [1, 1], 1)
+    assert CNOT(0, 1).propagate_pauli([3], [1]) == ([1, 0], [3, 3], 1)
+    assert SWAP(0, 1).propagate_pauli([1, 3], [0, 1]) == ([0, 1], [3, 1], 1)
+    with pytest.raises(ValueError):
+        T(0).propagate_pauli([1], [0])
+    with pytest.raises(ValueError):
+        H(0).propagate_pauli([1, 2], [0])
+
+
+def test_gate_copy() -> None:
+    import copy
+
+    gates = [
+        RX(0, 0.5),
+        UnitaryMatrix((0,), ((0, 1), (1, 0))),
+        ParametricRX(0),
+        ParametricPauliRotation((0, 1), (1, 3)),
+    ]
+    for gate in gates:
+        assert copy.copy(gate) == gate
+        assert copy.deepcopy(gate) == gate
+        assert gate.__deepcopy__({}) == gate
+
+
+def test_parametric_gate_bind() -> None:
+    assert ParametricRX(0).bind([0.5]) == RX(0, 0.5)
+    assert ParametricRY(1).bind([0.5]) == RY(1, 0.5)
+    assert ParametricRZ(2).bind([0.5]) == RZ(2, 0.5)
+    assert ParametricPauliRotation((0, 1), (1, 3)).bind([0.5]) == PauliRotation(
+        (0, 1), (1, 3), 0.5
+    )
+    with pytest.raises(ValueError):
+        ParametricRX(0).bind([])
+    with pytest.raises(ValueError):
+        ParametricRX(0).bind([0.1, 0.2])
+
+
+def test_parametric_gate_parameter_count() -> None:
+    assert ParametricRX(0).parameter_count == 1
+    assert ParametricRY(0).parameter_count == 1
+    assert ParametricRZ(0).parameter_count == 1
+    assert ParametricPauliRotation((0, 1), (1, 2)).parameter_count == 1
+    assert ParametricQuantumGate("Custom", (0,)).parameter_count == 0
+
+
+def test_gate_as_parametric() -> None:
+    assert RX(0, 0.5).as_parametric() == ParametricRX(0)
+    assert RY(1, 0.5).as_parametric() == ParametricRY(1)
+    assert RZ(2, 0.5).as_parametric() == ParametricRZ(2)
+    pauli_rotation = PauliRotation((0, 2), (1, 3), 0.5)
+    assert pauli_rotation.as_parametric() == ParametricPauliRotation((0, 2), (1, 3))
+    assert pauli_rotation.as_parametric().bind([0.5]) == pauli_rotation
+    with pytest.raises(ValueError):
+        H(0).as_parametric()
+
+
+def test_gate_commutes_with() -> None:
+    assert X(0).commutes_with(Z(1))
+    assert Measurement([0], [0]).commutes_with(H(1))
+    assert Z(0).commutes_with(CNOT(0, 1))
+    assert X(1).commutes_with(CNOT(0, 1))
+    assert not Z(1).commutes_with(CNOT(0, 1))
+    assert not X(0).commutes_with(Z(0))
+    assert RZ(0, 0.1).commutes_with(RZ(0, 0.2))
+    assert CNOT(0, 1).commutes_with(CNOT(0, 2))
+    assert not CNOT(0, 1).commutes_with(CNOT(1, 2))
+
+
+def test_gate_bytes() -> None:
+    gates = [
+        H(0),
+        RX(300, 0.1),
+        U3(0, 1.0 / 3.0, -1e-300, 2.5),
+        PauliRotation((0, 2), (1, 3), 0.7),
+        Measurement([0, 1], [1, 0]),
+        UnitaryMatrix((0,), ((0.5 + 0.5j, 0.5 - 0.5j), (0.5 - 0.5j, 0.5 + 0.5j))),
+    ]
+    for gate in gates:
+        data = gate.to_bytes()
+        assert isinstance(data, bytes)
+        assert data[:4] == b"QPG\x01"
+        assert QuantumGate.from_bytes(data) == gate
+    assert len(H(0).to_bytes()) < len(H(0).to_json())
+    with pytest.raises(ValueError):
+        QuantumGate.from_bytes(b"QPG\x02")
+    with pytest.raises(ValueError):
+        QuantumGate.from_bytes(H(0).to_bytes()[:-1])
+
+
+def test_gate_match_args() -> None:
+    assert QuantumGate.__match_args__ == (
+        "name",
+        "target_indices",
+        "control_indices",
+        "params",
+    )
+    gate = CNOT(0, 1)
+    assert tuple(getattr(gate, attr) for attr in QuantumGate.__match_args__) == (
+        "CNOT",
+        (1,),
+        (0,),
+        (),
+    )
+
+
+def test_gate_scale_params() -> None:
+    assert RX(0, 0.5).scale_params(0.5) == RX(0, 0.25)
+    assert RZ(1, 0.5).scale_params(-2.0) == RZ(1, -1.0)
+    assert U1(0, 0.5).scale_params(2.0) == U1(0, 1.0)
+    assert PauliRotation((0, 1), (1, 3), 0.5).scale_params(3.0) == PauliRotation(
+        (0, 1), (1, 3), 1.5
+    )
+    assert H(0).scale_params(2.0) == H(0)
+    assert CNOT(0, 1).scale_params(2.0) == CNOT(0, 1)
+    with pytest.raises(ValueError):
+        U3(0, 0.1, 0.2, 0.3).scale_params(2.0)
+    with pytest.raises(ValueError):
+        UnitaryMatrix((0,), ((1, 0), (0, 1))).scale_params(2.0)
+
+
+def test_gate_equiv_up_to_global_phase() -> None:
+    x = ((0, 1), (1, 0))
+    ix = ((0, 1j), (1j, 0))
+    assert UnitaryMatrix((0,), x).equiv_up_to_global_phase(UnitaryMatrix((0,), ix))
+    assert UnitaryMatrix((0,), ix).equiv_up_to_global_phase(X(0))
+    assert not UnitaryMatrix((0,), ix).equiv_up_to_global_phase(X(1))
+    assert not UnitaryMatrix((0,), x).equiv_up_to_global_phase(Z(0))
+    cnot = ((1, 0, 0, 0), (0, 0, 0, 1), (0, 0, 1, 0), (0, 1, 0, 0))
+    assert UnitaryMatrix((1, 0), cnot).equiv_up_to_global_phase(CNOT(1, 0))
+    assert not UnitaryMatrix((0, 1), cnot).equiv_up_to_global_phase(CNOT(1, 0))
+    assert RX(0, 0.5).equiv_up_to_global_phase(RX(0, 0.5 + 1e-12))
+    assert U1(0, 10.0).equiv_up_to_global_phase(U1(0, 10.0 - 4 * np.pi))
+    assert X(0).equiv_up_to_global_phase(Pauli([0], [1]))
+    assert not X(0).equiv_up_to_global_phase(Pauli([1], [1]))
+    assert not Measurement([0], [0]).equiv_up_to_global_phase(Measurement([0], [1]))
+
+
+def test_gate_classical_bits() -> None:
+    measurement = Measurement([0, 2], [1, 3])
+    assert measurement.is_measurement()
+    assert measurement.classical_bit_count == 2
+    assert not X(0).is_measurement()
+    assert X(0).classical_bit_count == 0
+
+
+def test_fuse_single_qubit() -> None:
+    from quri_parts.circuit.gate import fuse_single_qubit
+
+    gate = fuse_single_qubit([H(0), S(0), T(0)])
+    assert gate.name == gate_names.UnitaryMatrix
+    assert gate.target_indices == (0,)
+    expected = np.diag([1, np.exp(0.25j * np.pi)]) @ np.diag([1, 1j]) @ H(0).matrix()
+    assert np.allclose(gate.matrix(), expected)
+    assert fuse_single_qubit([X(1), Y(1)]).equiv_up_to_global_phase(Z(1))
+    with pytest.raises(ValueError):
+        fuse_single_qubit([])
+    with pytest.raises(ValueError):
+        fuse_single_qubit([H(0), X(1)])
+    with pytest.raises(ValueError):
+        fuse_single_qubit([CNOT(0, 1)])
+
+
+def test_tensor() -> None:
+    from quri_parts.circuit.gate import tensor
+
+    gate = tensor(X(2), Z(0))
+    assert gate.name == gate_names.UnitaryMatrix
+    assert gate.target_indices == (2, 0)
+    assert gate.equiv_up_to_global_phase(Pauli((0, 2), (3, 1)))
+    assert not gate.equiv_up_to_global_phase(Pauli((0, 2), (1, 3)))
+    assert tensor(H(1), CNOT(0, 2)).target_indices == (1, 0, 2)
+    with pytest.raises(ValueError):
+        tensor(X(0), CNOT(0, 1))
+
+
+def test_gate_canonical_repr() -> None:
+    assert (
+        RX(0, 0.5).canonical_repr()
+        == "RX(targets=[0];controls=[];classical=[];params=[0.5];pauli_ids=[];matrix=None)"
+    )
+    assert (
+        Pauli((2, 0), (1, 3)).canonical_repr()
+        == Pauli((0, 2), (3, 1)).canonical_repr()
+    )
+    assert RX(0, 0.0).canonical_repr() == RX(0, -0.0).canonical_repr()
+    assert CNOT(0, 1).canonical_repr() != CNOT(1, 0).canonical_repr()
+    assert (
+        UnitaryMatrix((0,), ((0, 1), (1, 0))).canonical_repr()
+        == "UnitaryMatrix(targets=[0];controls=[];classical=[];params=[];pauli_ids=[];"
+        "matrix=[[0.0:0.0,1.0:0.0],[1.0:0.0,0.0:0.0]])"
+    )
+
+
+def test_gate_angle() -> None:
+    assert RX(0, 0.1).angle() == 0.1
+    assert RY(0, 0.2).angle() == 0.2
+    assert RZ(0, 0.3).angle() == 0.3
+    assert PauliRotation((0, 1), (1, 2), 0.4).angle() == 0.4
+    with pytest.raises(ValueError):
+        H(0).angle()
+
+
+def test_gate_normalized() -> None:
+    assert RX(0, 0.5).normalized() == RX(0, 0.5)
+    assert RX(0, -np.pi).normalized() == RX(0, np.pi)
+    assert RZ(0, 3 * np.pi).normalized().isclose(RZ(0, np.pi))
+    assert RY(0, 2 * np.pi + 0.5).normalized().isclose(RY(0, 0.5))
+    assert U3(0, 7.0, -7.0, 0.5).normalized().isclose(
+        U3(0, 7.0 - 2 * np.pi, -7.0 + 2 * np.pi, 0.5)
+    )
+    assert PauliRotation((0,), (1,), -4.0).normalized().isclose(
+        PauliRotation((0,), (1,), 2 * np.pi - 4.0)
+    )
+    assert U1(0, 10.0).normalized().equiv_up_to_global_phase(U1(0, 10.0))
+    matrix_gate = UnitaryMatrix((0,), ((0, 1), (1, 0)))
+    assert matrix_gate.normalized() == matrix_gate
+
+
+def test_pauli_rotation_validation() -> None:
+    theta = 0.3
+    gate = PauliRotation((0, 1), (1, 3), theta)
+    xz = np.kron(np.array([[1, 0], [0, -1]]), np.array([[0, 1], [1, 0]]))
+    expected = np.cos(theta / 2) * np.eye(4) - 1j * np.sin(theta / 2) * xz
+    assert np.allclose(gate.matrix(), expected)
+    with pytest.raises(ValueError):
+        PauliRotation((0, 1), (1,), theta)
+    with pytest.raises(ValueError):
+        PauliRotation((0, 1), (1, 4), theta)
+    with pytest.raises(ValueError):
+        PauliRotation((0, 0), (1, 2), theta)
+    with pytest.raises(ValueError):
+        ParametricPauliRotation((0,), (0,))
+
+
+def test_pauli_ids_validation() -> None:
+    assert QuantumGate(
+        gate_names.Pauli, target_indices=(0, 1), pauli_ids=(0, 3)
+    ) == Pauli((0, 1), (0, 3))
+    with pytest.raises(ValueError, match="7"):
+        QuantumGate(gate_names.Pauli, target_indices=(0,), pauli_ids=(7,))
+    with pytest.raises(ValueError):
+        QuantumGate(gate_names.Pauli, target_indices=(0, 1), pauli_ids=(1,))
+    with pytest.raises(ValueError, match="7"):
+        Pauli((0,), (7,))
+    with pytest.raises(ValueError):
+        Pauli((0, 1), (1,))
+    with pytest.raises(ValueError):
+        Pauli((0, 0), (1, 2))
+    with pytest.raises(ValueError, match="0"):
+        QuantumGate(
+            gate_names.PauliRotation,
+            target_indices=(0,),
+            params=(0.1,),
+            pauli_ids=(0,),
+        )
+    with pytest.raises(ValueError, match="5"):
+        ParametricQuantumGate(
+            gate_names.ParametricPauliRotation, target_indices=(0,), pauli_ids=(5,)
+        )
+    with pytest.raises(ValueError):
+        ParametricQuantumGate(
+            gate_names.ParametricPauliRotation, target_indices=(0, 1), pauli_ids=(1,)
+        )
+
+
+def test_overlapping_indices_rejected() -> None:
+    with pytest.raises(ValueError):
+        CNOT(0, 0)
+    with pytest.raises(ValueError, match="both a target and a control"):
+        QuantumGate(gate_names.CNOT, target_indices=(0,), control_indices=(0,))
+    with pytest.raises(ValueError):
+        QuantumGate(gate_names.SWAP, target_indices=(1, 1))
+    with pytest.raises(ValueError):
+        QuantumGate(gate_names.Pauli, target_indices=(0, 0), pauli_ids=(1, 3))
+    assert QuantumGate(
+        gate_names.CNOT, target_indices=(1,), control_indices=(0,)
+    ) == CNOT(0, 1)
+
+
+def test_hash_consistent_with_eq() -> None:
+    lhs = PauliRotation((1, 2, 3), (3, 2, 1), 0.1)
+    rhs = PauliRotation((2, 1, 3), (2, 3, 1), 0.1)
+    assert lhs == rhs
+    assert hash(lhs) == hash(rhs)
+    assert RX(0, 0.0) == RX(0, -0.0)
+    assert hash(RX(0, 0.0)) == hash(RX(0, -0.0))
+    assert hash(TOFFOLI(0, 1, 2)) == hash(TOFFOLI(1, 0, 2))
+    assert len({CNOT(0, 1), CNOT(0, 1), CNOT(1, 0)}) == 2
+
+
+def test_euler_unitary_matrix() -> None:
+    from quri_parts.circuit.gate import u1, u2, u3
+
+    gate = u3(2, 0.3, -0.4, 1.1)
+    assert gate.name == gate_names.UnitaryMatrix
+    assert gate.target_indices == (2,)
+    assert np.allclose(gate.matrix(), U3(2, 0.3, -0.4, 1.1).matrix())
+    assert np.allclose(u2(0, 0.5, 0.7).matrix(), U2(0, 0.5, 0.7).matrix())
+    assert np.allclose(u1(0, 0.5).matrix(), U1(0, 0.5).matrix())
+    assert u3(0, np.pi, 0.0, np.pi).equiv_up_to_global_phase(X(0))
+
+
+def test_is_inverse_of() -> None:
+    assert H(0).is_inverse_of(H(0))
+    assert not H(0).is_inverse_of(H(1))
+    assert S(0).is_inverse_of(Sdag(0))
+    assert not S(0).is_inverse_of(S(0))
+    assert CNOT(0, 1).is_inverse_of(CNOT(0, 1))
+    assert RX(0, 0.3).is_inverse_of(RX(0, -0.3))
+    assert RZ(0, 0.3).is_inverse_of(RZ(0, 2 * np.pi - 0.3))
+    assert not RZ(0, 0.3).is_inverse_of(RZ(0, 0.3))
+    assert PauliRotation((0, 1), (1, 3), 0.2).is_inverse_of(
+        PauliRotation((1, 0), (3, 1), -0.2)
+    )
+    assert T(0).is_inverse_of(UnitaryMatrix((0,), Tdag(0).matrix()))
+    assert SWAP(0, 1).is_inverse_of(SWAP(1, 0))
+    assert not Measurement((0,), (0,)).is_inverse_of(Measurement((0,), (0,)))
+
+
+def test_negative_zero_dict_key() -> None:
+    keyed = {RX(0, 0.0): "rx"}
+    assert keyed[RX(0, -0.0)] == "rx"
+    lhs = UnitaryMatrix((0,), [[1, 0], [0, 1]])
+    rhs = UnitaryMatrix((0,), [[1, -0.0], [complex(-0.0, -0.0), 1]])
+    assert lhs == rhs
+    assert hash(lhs) == hash(rhs)
+    assert len({lhs, rhs, PauliRotation((0,), (3,), -0.0)}) == 2
+
+
+def test_gate_qubits() -> None:
+    assert TOFFOLI(5, 0, 3).qubits() == (0, 3, 5)
+    assert CNOT(2, 1).qubits() == (1, 2)
+    assert PauliRotation((4, 1, 2), (1, 2, 3), 0.1).qubits() == (1, 2, 4)
+    assert Measurement((3, 0), (0, 1)).qubits() == (0, 3)
+    assert X(7).qubits() == (7,)
+
+
+def test_from_qiskit() -> None:
+    assert QuantumGate.from_qiskit("cx", (0, 1)) == CNOT(0, 1)
+    assert QuantumGate.from_qiskit("rz", (2,), (0.5,)) == RZ(2, 0.5)
+    assert QuantumGate.from_qiskit("sx", (1,)) == SqrtX(1)
+    assert QuantumGate.from_qiskit("ccx", (0, 1, 2)) == TOFFOLI(0, 1, 2)
+    assert QuantumGate.from_qiskit("u", (0,), (0.1, 0.2, 0.3)) == U3(0, 0.1, 0.2, 0.3)
+    assert QuantumGate.from_qiskit("p", (0,), (0.4,)) == U1(0, 0.4)
+    with pytest.raises(ValueError, match="rzz"):
+        QuantumGate.from_qiskit("rzz", (0, 1), (0.1,))
+    with pytest.raises(ValueError):
+        QuantumGate.from_qiskit("rx", (0,))
+    with pytest.raises(ValueError):
+        QuantumGate.from_qiskit("cx", (0, 0))
+
+
+def test_sqrt_gates() -> None:
+    from quri_parts.circuit.gate import fuse_single_qubit
+
+    sqrt_x = np.array([[1 + 1j, 1 - 1j], [1 - 1j, 1 + 1j]]) / 2
+    sqrt_y = np.array([[1 + 1j, -1 - 1j], [1 + 1j, 1 + 1j]]) / 2
+    for gate, gate_dag, mat, square in [
+        (SqrtX(0), SqrtXdag(0), sqrt_x, X(0)),
+        (SqrtY(0), SqrtYdag(0), sqrt_y, Y(0)),
+    ]:
+        assert np.allclose(gate.matrix(), mat)
+        assert np.allclose(gate_dag.matrix(), mat.conj().T)
+        assert gate.inverse() == gate_dag
+        assert gate_dag.inverse() == gate
+        assert gate.is_clifford() and gate_dag.is_clifford()
+        assert QuantumGate(gate.name, target_indices=(0,)) == gate
+        assert fuse_single_qubit([gate, gate]).equiv_up_to_global_phase(square)
+
+
+def test_iswap_gates() -> None:
+    from quri_parts.circuit import ISWAP, SqrtISWAP
+
+    iswap = np.array([[1, 0, 0, 0], [0, 0, 1j, 0], [0, 1j, 0, 0], [0, 0, 0, 1]])
+    assert np.allclose(ISWAP(0, 1).matrix(), iswap)
+    sqrt_iswap = SqrtISWAP(0, 1).matrix()
+    assert np.allclose(np.array(sqrt_iswap) @ np.array(sqrt_iswap), iswap)
+    assert ISWAP(0, 1) == QuantumGate("ISWAP", target_indices=(0, 1))
+    assert ISWAP(0, 1).is_inverse_of(ISWAP(0, 1).inverse())
+    assert SqrtISWAP(2, 3).inverse().is_inverse_of(SqrtISWAP(2, 3))
+    assert ISWAP(0, 1).is_clifford()
+    assert not SqrtISWAP(0, 1).is_clifford()
+    with pytest.raises(ValueError):
+        ISWAP(1, 1)
+    with pytest.raises(ValueError):
+        SqrtISWAP(0, 0)
+
+
+def test_gate_pretty() -> None:
+    assert RX(0, np.pi / 4).pretty() == "RX(0.785) on q0"
+    assert CNOT(0, 1).pretty() == "CNOT q0->q1"
+    assert TOFFOLI(0, 1, 2).pretty() == "TOFFOLI q0,q1->q2"
+    assert Measurement((0, 1), (1, 0)).pretty() == "Measurement q0->c1,q1->c0"
+    assert PauliRotation((0, 2), (1, 3), 0.5).pretty() == "PauliRotation(XZ, 0.5) on q0,q2"
+    assert U3(0, 1.0, 2.5, -3.0).pretty() == "U3(1, 2.5, -3) on q0"
+    assert repr(X(0)).startswith("QuantumGate(name='X'")
+
+
+def test_gate_as_dict() -> None:
+    assert CNOT(0, 1).as_dict() == {
+        "name": gate_names.CNOT,
+        "target_indices": (1,),
+        "control_indices": (0,),
+        "classical_indices": (),
+        "params": (),
+        "pauli_ids": (),
+        "unitary_matrix": None,
+    }
+    d = PauliRotation((0, 2), (1, 3), 0.5).as_dict()
+    assert d["params"] == (0.5,)
+    assert d["pauli_ids"] == (1, 3)
+    assert UnitaryMatrix((0,), [[0, 1], [1, 0]]).as_dict()["unitary_matrix"] == (
+        (0, 1),
+        (1, 0),
+    )
+    assert QuantumGate(**Measurement((0,), (2,)).as_dict()) == Measurement((0,), (2,))
+
+
+def test_gate_from_dict() -> None:
+    for gate in [
+        CNOT(0, 1),
+        PauliRotation((0, 2), (1, 3), 0.5),
+        Measurement((0, 1), (1, 0)),
+        UnitaryMatrix((0,), [[0, 1], [1, 0]]),
+    ]:
+        assert QuantumGate.from_dict(gate.as_dict()) == gate
+    assert QuantumGate.from_dict({"name": "X", "target_indices": [3]}) == X(3)
+    with pytest.raises(ValueError, match="target_indices"):
+        QuantumGate.from_dict({"name": "X"})
+    with pytest.raises(ValueError, match="targets"):
+        QuantumGate.from_dict({"name": "X", "targets": [0]})
+    with pytest.raises(ValueError):
+        QuantumGate.from_dict(
+            {"name": "CNOT", "target_indices": [0], "control_indices": [0]}
+        )
+
+
+def test_gate_fidelity() -> None:
+    from quri_parts.circuit import ECR
+
+    assert RX(0, 0.3).process_fidelity(RX(0, 0.3)) == pytest.approx(1.0)
+    assert RX(0, 0.3).process_fidelity(RX(0, 0.0)) == pytest.approx(
+        (2 * np.cos(0.15) ** 2 + 1) / 3
+    )
+    assert X(0).process_fidelity(Z(0)) == pytest.approx(1 / 3)
+    assert RZ(0, 0.6).process_fidelity(Identity(0)) == pytest.approx(
+        (2 * np.cos(0.3) ** 2 + 1) / 3
+    )
+    assert H(0).process_fidelity(H(0)) <= 1.0
+    assert ECR(0, 1).process_fidelity(ECR(0, 1)) <= 1.0
+    cnot_matrix = CNOT(0, 1).matrix()
+    assert CNOT(0, 1).process_fidelity(
+        UnitaryMatrix((0, 1), cnot_matrix)
+    ) == pytest.approx(1.0)
+    assert CNOT(0, 1).process_fidelity(
+        UnitaryMatrix((1, 0), cnot_matrix)
+    ) == pytest.approx(0.25)
+    with pytest.raises(ValueError):
+        X(0).process_fidelity(X(1))
+
+
+def test_unitary_from_buffer() -> None:
+    flat = np.array(CNOT(0, 1).matrix(), dtype=np.complex128).reshape(-1)
+    gate = QuantumGate.unitary_from_buffer((0, 1), flat, 4)
+    assert gate == UnitaryMatrix((0, 1), CNOT(0, 1).matrix())
+    assert QuantumGate.unitary_from_buffer((2,), [0, 1, 1, 0], 2) == UnitaryMatrix(
+        (2,), [[0, 1], [1, 0]]
+    )
+    with pytest.raises(ValueError):
+        QuantumGate.unitary_from_buffer((0,), [0, 1, 1], 2)
+    with pytest.raises(ValueError):
+        QuantumGate.unitary_from_buffer((0, 1), [0, 1, 1, 0], 2)
+    with pytest.raises(ValueError):
+        QuantumGate.unitary_from_buffer((0,), [1, 1, 1, 1], 2)
+
+
+def test_is_diagonal() -> None:
+    for gate in [Z(0), S(0), T(0), RZ(0, 0.3), CZ(0, 1), U1(0, 0.2)]:
+        assert gate.is_diagonal()
+    assert Pauli((0, 1), (3, 3)).is_diagonal()
+    assert PauliRotation((0, 1), (3, 3), 0.4).is_diagonal()
+    assert not PauliRotation((0, 1), (3, 1), 0.4).is_diagonal()
+    for gate in [X(0), H(0), CNOT(0, 1), RX(0, 0.3)]:
+        assert not gate.is_diagonal()
+    assert UnitaryMatrix((0,), [[1, 0], [0, 1j]]).is_diagonal()
+    assert not UnitaryMatrix((0,), [[0, 1], [1, 0]]).is_diagonal()
+    assert RX(0, 1e-12).is_diagonal(atol=1e-10)
+    assert not Measurement((0,), (0,)).is_diagonal()
+
+
+def test_nnz_and_is_permutation() -> None:
+    assert X(0).nnz() == 2
+    assert H(0).nnz() == 4
+    assert CNOT(0, 1).nnz() == 4
+    assert TOFFOLI(0, 1, 2).nnz() == 8
+    assert RX(0, 0.3).nnz() == 4
+    assert RX(0, 0.0).nnz() == 2
+    for gate in [X(0), Y(0), S(0), CNOT(0, 1), SWAP(0, 1), TOFFOLI(0, 1, 2)]:
+        assert gate.is_permutation()
+    for gate in [H(0), RX(0, 0.3), SqrtX(0)]:
+        assert not gate.is_permutation()
+    assert UnitaryMatrix((0,), [[0, 1j], [1, 0]]).is_permutation()
+    with pytest.raises(ValueError):
+        Measurement((0,), (0,)).nnz()
+
+
+def test_measurement_pairs() -> None:
+    assert Measurement((2, 0), (1, 3)).measurement_pairs() == [(2, 1), (0, 3)]
+    with pytest.raises(ValueError):
+        X(0).measurement_pairs()
+    with pytest.raises(ValueError):
+        QuantumGate(gate_names.Measurement, target_indices=(0, 1), classical_indices=(0,))
+
+
+def test_apply_gate() -> None:
+    from quri_parts.circuit.gate import apply_gate
+
+    state = np.zeros(4, dtype=np.complex128)
+    state[0] = 1.0
+    apply_gate(state, H(0), 2)
+    apply_gate(state, CNOT(0, 1), 2)
+    assert np.allclose(state, np.array([1, 0, 0, 1]) / np.sqrt(2))
+    apply_gate(state, RZ(1, np.pi), 2)
+    assert np.allclose(state, np.array([-1j, 0, 0, 1j]) / np.sqrt(2))
+
+    with pytest.raises(ValueError):
+        apply_gate(np.zeros(8, dtype=np.complex128), X(0), 2)
+    with pytest.raises(ValueError):
+        apply_gate(state, X(2), 2)
+    with pytest.raises(TypeError):
+        apply_gate(np.zeros(4, dtype=np.float64), X(0), 2)
+
+
+def test_shift_rule() -> None:
+    for gate in [ParametricRX(0), ParametricPauliRotation((0, 1), (1, 3))]:
+        rule = gate.shift_rule()
+        assert rule == [(0.5, np.pi / 2), (-0.5, -np.pi / 2)]
+
+    # d/dt <0|RX(t)^dag Z RX(t)|0> = -sin(t).
+    theta = 0.3
+    grad = sum(c * np.cos(theta + s) for c, s in ParametricRX(0).shift_rule())
+    assert np.isclose(grad, -np.sin(theta))
+
+    with pytest.raises(ValueError):
+        ParametricQuantumGate("Custom", (0,)).shift_rule()
+
+
+def test_is_identity() -> None:
+    assert Identity(0).is_identity()
+    assert RZ(0, 0.0).is_identity()
+    assert RX(1, 2 * np.pi + 1e-12).is_identity()
+    assert not RX(1, 2 * np.pi).is_identity(up_to_global_phase=False)
+    assert not RX(1, 0.1).is_identity()
+    assert not X(0).is_identity()
+    assert not Measurement([0], [0]).is_identity()
+
+    near = np.exp(0.5j) * np.array([[1, 1e-12], [0, 1]])
+    assert SingleQubitUnitaryMatrix(0, near.tolist()).is_identity()
+    assert not SingleQubitUnitaryMatrix(0, near.tolist()).is_identity(
+        up_to_global_phase=False
+    )
+
+
+def test_expected_matrix() -> None:
+    assert CNOT(0, 1).expected_matrix() == CNOT(0, 1).matrix()
+    # Qubit i is bit i, so the controls 0 and 1 flip the target between the basis
+    # states 3 and 7.
+    assert np.allclose(
+        TOFFOLI(0, 1, 2).expected_matrix(), np.eye(8)[[0, 1, 2, 7, 4, 5, 6, 3]]
+    )
+
+    # The matrix supplied with a named controlled gate is not the reference.
+    custom = QuantumGate("CNOT", (1,), (0,), unitary_matrix=[[0, 1], [1, 0]])
+    assert np.allclose(custom.expected_matrix(), CNOT(0, 1).matrix())
+
+    x = np.array([[0, 1], [1, 0]])
+    assert np.allclose(UnitaryMatrix((0,), x.tolist()).expected_matrix(), x)
+    with pytest.raises(ValueError):
+        Measurement([0], [0]).expected_matrix()
+    with pytest.raises(ValueError):
+        QuantumGate("Custom", (0,), unitary_matrix=x.tolist()).expected_matrix()
+
+
+def test_degrees() -> None:
+    from quri_parts.circuit import RX_deg, RY_deg, RZ_deg
+
+    assert RX_deg(0, 90.0) == RX(0, np.pi / 2)
+    assert RY_deg(1, -180.0).params == (-np.pi,)
+    assert RZ_deg(2, 45.0).angle_degrees() == pytest.approx(45.0)
+    assert PauliRotation((0, 1), (1, 2), np.pi).angle_degrees() == pytest.approx(180.0)
+    with pytest.raises(ValueError):
+        X(0).angle_degrees()
+
+
+def test_gates_from_arrays() -> None:
+    from quri_parts.circuit.gate import gates_from_arrays
+
+    gates = gates_from_arrays(
+        [gate_names.H, gate_names.RX, gate_names.CNOT, gate_names.Measurement],
+        [(0,), (1,), (1,), (0, 1)],
+        params_list=[(), (0.5,), (), ()],
+        control_indices_list=[(), (), (0,), ()],
+        classical_indices_list=[(), (), (), (1, 0)],
+    )
+    assert gates == [H(0), RX(1, 0.5), CNOT(0, 1), Measurement([0, 1], [1, 0])]
+    assert gates_from_arrays([gate_names.X] * 3, [(0,), (1,), (2,)]) == [
+        X(0),
+        X(1),
+        X(2),
+    ]
+
+    with pytest.raises(ValueError, match="target_indices_list"):
+        gates_from_arrays([gate_names.X], [(0,), (1,)])
+    with pytest.raises(ValueError, match="params_list"):
+        gates_from_arrays([gate_names.X, gate_names.X], [(0,), (1,)], [()])
+    with pytest.raises(ValueError):
+        gates_from_arrays([gate_names.CNOT], [(0,)], control_indices_list=[(0,)])
+
+
+def test_with_swapped_order() -> None:
+    cnot = UnitaryMatrix((3, 5), CNOT(0, 1).matrix())
+    swapped = cnot.with_swapped_order()
+    assert swapped.target_indices == (5, 3)
+    assert np.allclose(swapped.matrix(), CNOT(0, 1).matrix(endian="big"))
+    assert cnot.equiv_up_to_global_phase(swapped)
+    assert swapped.with_swapped_order() == cnot
+
+    with pytest.raises(ValueError):
+        CNOT(0, 1).with_swapped_order()
+    with pytest.raises(ValueError):
+        UnitaryMatrix((0,), [[0, 1], [1, 0]]).with_swapped_order()
+
+
+def test_global_phase() -> None:
+    from quri_parts.circuit import GlobalPhase
+
+    gate = GlobalPhase(0, 0.5)
+    assert gate == QuantumGate("GlobalPhase", target_indices=(0,), params=(0.5,))
+    assert np.allclose(gate.matrix(), np.exp(0.5j) * np.eye(2))
+    assert gate.inverse() == GlobalPhase(0, -0.5)
+    assert gate.is_inverse_of(gate.inverse())
+    assert gate.is_identity()
+    assert not gate.is_identity(up_to_global_phase=False)
+    # Controlling a global phase leaves a relative phase on the control qubit.
+    assert gate.controlled(1) == U1(1, 0.5)
+
+    # U3(theta, phi, lmd) = e^{i(phi+lmd)/2} RZ(phi) RY(theta) RZ(lmd).
+    theta, phi, lmd = 0.1, 0.2, 0.3
+    product = (
+        np.array(RZ(0, phi).matrix())
+        @ np.array(RY(0, theta).matrix())
+        @ np.array(RZ(0, lmd).matrix())
+    )
+    decomposed = np.array(GlobalPhase(0, (phi + lmd) / 2).matrix()) @ product
+    assert np.allclose(decomposed, U3(0, theta, phi, lmd).matrix())
+
+
+def test_to_cirq_spec() -> None:
+    assert CNOT(2, 0).to_cirq_spec() == ("CX", [2, 0], [])
+    assert TOFFOLI(0, 1, 2).to_cirq_spec() == ("CCX", [0, 1, 2], [])
+    assert RX(1, 0.5).to_cirq_spec() == ("rx", [1], [0.5])
+    assert Sdag(0).to_cirq_spec() == ("ZPowGate", [0], [-0.5])
+    name, qubits, params = U1(3, np.pi / 4).to_cirq_spec()
+    assert (name, qubits) == ("ZPowGate", [3])
+    assert params == pytest.approx([0.25])
+
+    for gate in [U3(0, 0.1, 0.2, 0.3), Measurement([0], [0]), Pauli((0, 1), (1, 2))]:
+        with pytest.raises(ValueError):
+            gate.to_cirq_spec()
+
+
+def test_content_hash() -> None:
+    # Pinned values: the hash must not change between runs or releases.
+    assert RX(0, 0.5).content_hash() == 0xAF2E0CB0BA7BBE4099D8A2F55F53B32E
+    assert CNOT(0, 1).content_hash() == 0x81ECFBBF1F4CBA1005E007B81A61BA8E
+
+    for _ in range(3):
+        assert RX(0, 0.5).content_hash() == RX(0, 0.5).content_hash()
+        assert (
+            UnitaryMatrix((0,), [[0, 1], [1, 0]]).content_hash()
+            == UnitaryMatrix((0,), [[0, 1], [1, 0]]).content_hash()
+        )
+    assert Pauli((1, 0), (1, 3)).content_hash() == Pauli((0, 1), (3, 1)).content_hash()
+    assert RX(0, -0.0).content_hash() == RX(0, 0.0).content_hash()
+    assert RX(0, 0.5).content_hash() != RY(0, 0.5).content_hash()
+    assert 0 <= CNOT(0, 1).content_hash() < 2**128
+
+
+def test_pauli_string() -> None:
+    assert Pauli((0, 1, 2), (1, 0, 3)).pauli_string() == "X0 Z2"
+    assert PauliRotation((3, 1), (2, 1), 0.5).pauli_string() == "Y3 X1"
+    assert Z(4).pauli_string(strict=True) == "Z4"
+    assert H(0).pauli_string() == ""
+    with pytest.raises(ValueError):
+        H(0).pauli_string(strict=True)
+
+
+def test_trace_and_determinant() -> None:
+    assert Identity(0).trace() == pytest.approx(2)
+    assert RX(0, 0.5).trace() == pytest.approx(2 * np.cos(0.25))
+    assert CNOT(0, 1).trace() == pytest.approx(2)
+    assert X(0).determinant() == pytest.approx(-1)
+    assert S(0).determinant() == pytest.approx(1j)
+    assert RZ(0, 0.3).determinant() == pytest.approx(1)
+    assert PauliRotation((0, 1), (1, 2), 0.7).determinant() == pytest.approx(1)
+    assert SWAP(0, 1).determinant() == pytest.approx(-1)
+
+    u = np.exp(0.25j) * np.array([[0, 1], [1, 0]])
+    assert UnitaryMatrix((0,), u.tolist()).determinant() == pytest.approx(
+        np.linalg.det(u)
+    )
+    with pytest.raises(ValueError):
+        Measurement([0], [0]).trace()
+
+
+def test_parametric_substitute() -> None:
+    theta = object()
+    param_map = {theta: 0.5, "phi": -0.25}
+    assert ParametricRX(0).substitute(param_map, [theta]) == RX(0, 0.5)
+    assert ParametricRZ(1).substitute(param_map, [theta]) == RZ(1, 0.5)
+    assert ParametricPauliRotation((0, 1), (1, 3)).substitute(
+        param_map, ["phi"]
+    ) == PauliRotation((0, 1), (1, 3), -0.25)
+
+    with pytest.raises(KeyError):
+        ParametricRY(0).substitute(param_map, ["psi"])
+    with pytest.raises(ValueError):
+        ParametricRY(0).substitute(param_map, [theta, "phi"])
+
+
+def test_resource_counts() -> None:
+    from quri_parts.circuit.gate import count_by_name, t_count, two_qubit_count
+
+    gates = [T(0), H(1), CNOT(0, 1), Tdag(1), T(2), TOFFOLI(0, 1, 2), SWAP(1, 2)]
+    assert count_by_name(gates) == {
+        gate_names.T: 2,
+        gate_names.H: 1,
+        gate_names.CNOT: 1,
+        gate_names.Tdag: 1,
+        gate_names.TOFFOLI: 1,
+        gate_names.SWAP: 1,
+    }
+    assert t_count(gates) == 3
+    assert two_qubit_count(gates) == 2
+    assert count_by_name([]) == {}
+
+
+def test_controlled_rotations() -> None:
+    from quri_parts.circuit import CRX, CRY, CRZ
+
+    for factory, rotation, name in [
+        (CRX, RX, "CRX"),
+        (CRY, RY, "CRY"),
+        (CRZ, RZ, "CRZ"),
+    ]:
+        gate = factory(0, 1, 0.7)
+        assert gate == QuantumGate(name, (1,), (0,), params=(0.7,))
+        assert gate.name == name
+        assert gate.control_indices == (0,)
+        assert gate.target_indices == (1,)
+        assert gate.kind == "Rotation"
+        assert rotation(1, 0.7).controlled(0) == gate
+        assert gate.inverse() == factory(0, 1, -0.7)
+
+        r = np.array(rotation(0, 0.7).matrix())
+        expected = np.eye(4, dtype=complex)
+        expected[1::2, 1::2] = r
+        assert np.allclose(gate.matrix(), expected)
+
+        # Qubit 0 is the lower bit, and the decomposition only acts on q1 alone.
+        decomposed = np.eye(4)
+        for g in gate.decompose():
+            m = g.matrix() if g.qubit_count == 2 else np.kron(g.matrix(), np.eye(2))
+            decomposed = np.array(m) @ decomposed
+        assert np.allclose(decomposed, expected)
+
+        assert QuantumGate.from_qasm(gate.to_qasm()) == gate
+        with pytest.raises(ValueError):
+            factory(1, 1, 0.7)
+
+    # Without the control the name does not form a CRX gate.
+    assert QuantumGate("CRX", (1,), params=(0.7,)).kind == "Other"
+
+
+def test_inverse_sequence() -> None:
+    from quri_parts.circuit.gate import inverse_sequence
+
+    gates = [H(0), S(1), CNOT(0, 1), RX(1, 0.3), T(0)]
+    assert inverse_sequence(gates) == [
+        Tdag(0),
+        RX(1, -0.3),
+        CNOT(0, 1),
+        Sdag(1),
+        H(0),
+    ]
+    assert inverse_sequence([]) == []
+    with pytest.raises(ValueError):
+        inverse_sequence([H(0), Measurement([0], [0])])
+
+
+def test_error_types() -> None:
+    from quri_parts.circuit.gate import (
+        IndexConflictError,
+        InvalidUnitaryError,
+        UnknownGateError,
+    )
+
+    with pytest.raises(UnknownGateError, match="ParametricRX"):
+        QuantumGate("ParametricRX", (0,))
+    with pytest.raises(UnknownGateError, match="nosuchgate"):
+        QuantumGate.from_qasm("nosuchgate q[0];")
+    with pytest.raises(UnknownGateError, match="rzz"):
+        QuantumGate.from_qiskit("rzz", [0, 1], [0.1])
+    with pytest.raises(InvalidUnitaryError):
+        QuantumGate("UnitaryMatrix", (0,), unitary_matrix=[[1, 0, 0], [0, 1, 0]])
+    with pytest.raises(InvalidUnitaryError):
+        UnitaryMatrix((0,), [[1, 1], [0, 1]])
+    with pytest.raises(IndexConflictError, match="CNOT"):
+        QuantumGate("CNOT", (0,), (0,))
+    with pytest.raises(IndexConflictError, match="SWAP"):
+        SWAP(1, 1)
+    # The specific errors are still ValueErrors.
+    for error in (UnknownGateError, InvalidUnitaryError, IndexConflictError):
+        assert issubclass(error, ValueError)
+
+
+def test_merge_rotations() -> None:
+    from quri_parts.circuit import CRX, CRY
+    from quri_parts.circuit.gate import merge_rotations
+
+    assert merge_rotations(RZ(0, 0.1), RZ(0, 0.2)) == RZ(0, 0.1 + 0.2)
+    assert merge_rotations(CRY(0, 1, 0.1), CRY(0, 1, -0.1)) == CRY(0, 1, 0.0)
+    assert merge_rotations(
+        PauliRotation((0, 1), (1, 2), 0.1), PauliRotation((0, 1), (1, 2), 0.2)
+    ) == PauliRotation((0, 1), (1, 2), 0.1 + 0.2)
+    assert merge_rotations(RZ(0, 0.1), RZ(1, 0.2)) is None
+    assert merge_rotations(RZ(0, 0.1), RY(0, 0.2)) is None
+    assert merge_rotations(CRX(0, 1, 0.1), CRX(1, 0, 0.2)) is None
+    assert (
+        merge_rotations(
+            PauliRotation((0, 1), (1, 2), 0.1), PauliRotation((1, 0), (2, 1), 0.2)
+        )
+        is None
+    )
+    assert merge_rotations(H(0), H(0)) is None
+
+
+def test_supported_gates() -> None:
+    from quri_parts.circuit.gate import supported_gates
+
+    schema = {d["name"]: d for d in supported_gates()}
+    assert schema["CRX"] == {
+        "name": "CRX",
+        "targets": 1,
+        "controls": 1,
+        "classical": 0,
+        "params": 1,
+        "pauli_ids": 0,
+        "unitary_matrix": False,
+        "has_matrix": True,
+    }
+    assert schema["PauliRotation"]["targets"] == "any"
+    assert schema["PauliRotation"]["pauli_ids"] == "per_target"
+    assert schema["Measurement"]["classical"] == "per_target"
+    assert not schema["Measurement"]["has_matrix"]
+    # Every described gate can be constructed from its description.
+    for d in schema.values():
+        if d["name"] in ("UnitaryMatrix", "Measurement", "Pauli", "PauliRotation"):
+            continue
+        gate = QuantumGate(
+            d["name"],
+            target_indices=range(d["targets"]),
+            control_indices=range(10, 10 + d["controls"]),
+            params=[0.5] * d["params"],
+        )
+        assert gate.kind != "Other"
+        assert gate.is_unitary() == d["has_matrix"]
+
+
+def test_gate_str() -> None:
+    assert str(RX(0, np.pi / 4)) == "RX(θ=0.79) q0"
+    assert str(CNOT(0, 1)) == "CNOT q0 q1"
+    assert str(U3(0, 1.0, 2.5, -3.0)) == "U3(θ=1, φ=2.5, λ=-3) q0"
+    assert str(Pauli((0, 1), (3, 0))) == "Pauli(ZI) q0 q1"
+    assert str(Measurement((0, 1), (1, 0))) == "Measurement q0->c1,q1->c0"
+    # The repr stays the full reconstructible form.
+    assert repr(H(0)).startswith("QuantumGate(name='H'")
+
+
+def test_locality() -> None:
+    # Identity placeholders do not count towards the weight of a Pauli gate.
+    assert Pauli((0, 1, 2), (1, 0, 3)).locality() == 2
+    assert Pauli((0, 1), (1, 3)).locality() == 2
+    assert Pauli((0, 1), (0, 0)).locality() == 0
+    assert Pauli((0, 1, 2), (1, 0, 3)).qubit_count == 3
+    assert PauliRotation((0, 2), (1, 3), 0.5).locality() == 2
+    assert CNOT(0, 1).locality() == 2
+    assert TOFFOLI(0, 1, 2).locality() == 3
+    assert X(3).locality() == 1
+
+
+def test_repr_roundtrip() -> None:
+    namespace = {"QuantumGate": QuantumGate}
+    gates = [
+        RX(0, 0.1 + 0.2),
+        U3(1, 1e-300, -0.0, 3.0),
+        CNOT(0, 1),
+        Measurement((0, 1), (1, 0)),
+        PauliRotation((0, 2), (1, 3), np.pi),
+        UnitaryMatrix((0,), [[0, -1j], [1j, 0]]),
+        UnitaryMatrix((0,), [[np.sqrt(0.5), np.sqrt(0.5)], [np.sqrt(0.5), -np.sqrt(0.5)]]),
+    ]
+    for gate in gates:
+        assert eval(repr(gate), namespace) == gate
+    assert "params=(1.0,)" in repr(RX(0, 1.0))
+
+
+def test_to_matrix_gate() -> None:
+    from quri_parts.circuit.gate import apply_gate
+
+    for gate in [H(2), CNOT(2, 0), TOFFOLI(0, 2, 1), RX(1, 0.3), Pauli((0, 2), (1, 2))]:
+        lowered = gate.to_matrix_gate()
+        assert lowered.name == "UnitaryMatrix"
+        assert lowered.qubits() == gate.qubits()
+        state = np.arange(8, dtype=np.complex128) / np.sqrt(140)
+        expected = state.copy()
+        apply_gate(expected, gate, 3)
+        apply_gate(state, lowered, 3)
+        assert np.allclose(state, expected)
+    unitary = UnitaryMatrix((1, 0), CNOT(0, 1).matrix())
+    assert unitary.to_matrix_gate() == unitary
+    with pytest.raises(ValueError):
+        Measurement((0,), (0,)).to_matrix_gate()
+
+
+def test_duration() -> None:
+    import copy
+    import pickle
+
+    gate = CNOT(0, 1)
+    assert gate.duration is None
+    timed = gate.with_duration(35.5)
+    assert timed.duration == 35.5
+    assert gate.duration is None
+    # The duration is metadata and does not affect equality or hashing.
+    assert timed == gate
+    assert hash(timed) == hash(gate)
+    assert timed.with_duration(10.0).duration == 10.0
+    assert copy.copy(timed).duration == 35.5
+    assert pickle.loads(pickle.dumps(timed)).duration is None
+    assert timed.inverse().duration is None
+    with pytest.raises(ValueError):
+        gate.with_duration(-1.0)
+    with pytest.raises(ValueError):
+        gate.with_duration(float("nan"))
+
+
+def test_diagonalizing_circuit() -> None:
+    assert X(0).diagonalizing_circuit() == [H(0)]
+    assert Y(1).diagonalizing_circuit() == [Sdag(1), H(1)]
+    assert Z(2).diagonalizing_circuit() == []
+    assert Pauli((0, 1, 2, 3), (1, 0, 2, 3)).diagonalizing_circuit() == [
+        H(0),
+        Sdag(2),
+        H(2),
+    ]
+    assert PauliRotation((4,), (2,), 0.3).diagonalizing_circuit() == [Sdag(4), H(4)]
+    assert Measurement((0,), (0,)).diagonalizing_circuit() == []
+    # The basis change maps the Pauli to Z.
+    basis_change = np.array(H(0).matrix()) @ np.array(Sdag(0).matrix())
+    assert np.allclose(basis_change @ Y(0).matrix() @ basis_change.conj().T, Z(0).matrix())
+    with pytest.raises(ValueError):
+        H(0).diagonalizing_circuit()
+
+
+def test_param_labels() -> None:
+    import pickle
+
+    plain = ParametricQuantumGate("ParametricRX", (0,))
+    labelled = ParametricQuantumGate("ParametricRX", (0,), param_labels=["theta"])
+    assert plain.param_labels is None
+    assert labelled.param_labels == ["theta"]
+    # Unlabelled gates compare as before; labels take part once present.
+    assert plain == ParametricRX(0)
+    assert labelled != plain
+    assert labelled != ParametricQuantumGate("ParametricRX", (0,), param_labels=["phi"])
+    assert labelled == ParametricQuantumGate("ParametricRX", (0,), param_labels=["theta"])
+    assert hash(labelled) == hash(
+        ParametricQuantumGate("ParametricRX", (0,), param_labels=["theta"])
+    )
+    assert pickle.loads(pickle.dumps(labelled)) == labelled
+    assert eval(repr(labelled), {"ParametricQuantumGate": ParametricQuantumGate}) == labelled
+    # Labels do not change the bound gate.
+    assert labelled.bind([0.5]) == plain.bind([0.5])
+    with pytest.raises(ValueError):
+        ParametricQuantumGate("ParametricRX", (0,), param_labels=["a", "b"])
+
+
+def test_power() -> None:
+    from quri_parts.circuit import ISWAP, SqrtISWAP
+
+    assert RZ(0, 0.25).power(3) == RZ(0, 0.75)
+    assert PauliRotation((0, 1), (1, 2), 0.1).power(-2) == PauliRotation(
+        (0, 1), (1, 2), -0.2
+    )
+    assert H(2).power(3) == H(2)
+    assert H(2).power(4) == Identity(2)
+    assert CNOT(0, 1).power(2) == Pauli((0, 1), (0, 0))
+    assert X(0).power(0) == Identity(0)
+    assert T(0).power(5).name == "UnitaryMatrix"
+    assert np.allclose(T(0).power(2).matrix(), S(0).matrix())
+    assert np.allclose(T(0).power(-2).matrix(), Sdag(0).matrix())
+    unitary = UnitaryMatrix((1, 0), SqrtISWAP(0, 1).matrix())
+    squared = unitary.power(2)
+    assert squared.target_indices == (1, 0)
+    assert np.allclose(squared.matrix(), ISWAP(0, 1).matrix())
+    with pytest.raises(ValueError):
+        Measurement((0,), (0,)).power(2)
+
+
+def test_matrix_endian() -> None:
+    little = np.array(CNOT(0, 1).matrix())
+    assert np.allclose(little, CNOT(0, 1).matrix(endian="little"))
+    big = np.array(CNOT(0, 1).matrix(endian="big"))
+    assert np.allclose(
+        big, [[1, 0, 0, 0], [0, 1, 0, 0], [0, 0, 0, 1], [0, 0, 1, 0]]
+    )
+    # For two qubits this swaps basis states 01 and 10.
+    assert np.allclose(big, little[[0, 2, 1, 3]][:, [0, 2, 1, 3]])
+    assert np.allclose(H(0).matrix(endian="big"), H(0).matrix())
+    with pytest.raises(ValueError):
+        H(0).matrix(endian="middle")
+
+
+def test_to_stim() -> None:
+    assert CNOT(0, 1).to_stim() == "CX 0 1"
+    assert H(2).to_stim() == "H 2"
+    assert Sdag(3).to_stim() == "S_DAG 3"
+    assert SWAP(4, 1).to_stim() == "SWAP 4 1"
+    assert Measurement((0, 2), (1, 0)).to_stim() == "M 0 2"
+    assert Pauli((0, 5), (2, 3)).to_stim() == "Y 0\nZ 5"
+    for gate in [T(0), RX(0, 0.5), TOFFOLI(0, 1, 2)]:
+        with pytest.raises(ValueError):
+            gate.to_stim()
+
+
+def test_classical_indices_only_for_measurements() -> None:
+    with pytest.raises(ValueError, match="RX gate cannot have classical indices"):
+        QuantumGate("RX", (0,), classical_indices=(0,), params=(0.5,))
+    with pytest.raises(ValueError):
+        QuantumGate.from_dict(
+            {
+                "name": "CNOT",
+                "target_indices": [1],
+                "control_indices": [0],
+                "classical_indices": [0],
+            }
+        )
+    assert QuantumGate("Measurement", (0,), classical_indices=(1,)).classical_indices == (1,)
+    # User-defined gates may still write classical bits.
+    assert QuantumGate("Reset", (0,), classical_indices=(0,)).kind == "Other"
+
+
+def test_to_qasm3() -> None:
+    from quri_parts.circuit import ISWAP, GlobalPhase
+
+    assert CNOT(0, 1).to_qasm3() == "cx q[0], q[1];"
+    assert RX(2, 0.5).to_qasm3() == "rx(0.5) q[2];"
+    assert U1(0, 0.25).to_qasm3() == "p(0.25) q[0];"
+    assert SqrtXdag(1).to_qasm3() == "inv @ sx q[1];"
+    assert GlobalPhase(0, 1.0).to_qasm3() == "gphase(1.0);"
+    assert Measurement((0, 1), (1, 0)).to_qasm3() == (
+        "c[1] = measure q[0];\nc[0] = measure q[1];"
+    )
+    assert ParametricRZ(3).to_qasm3("theta") == "rz(theta) q[3];"
+    with pytest.raises(ValueError):
+        ParametricRZ(3).to_qasm3("1theta")
+    with pytest.raises(ValueError):
+        ParametricPauliRotation((0, 1), (1, 2)).to_qasm3("theta")
+    with pytest.raises(ValueError):
+        ISWAP(0, 1).to_qasm3()
+
+
+def test_gate_diff() -> None:
+    from quri_parts.circuit.gate import gate_diff
+
+    assert gate_diff(RX(0, 0.5), RX(0, 0.5)) is None
+    assert gate_diff(RX(0, 0.5), RX(0, 0.5).with_duration(10.0)) is None
+    assert gate_diff(RX(0, 0.5), RY(1, 0.75)) == (
+        "name: RX -> RY\ntarget_indices: [0] -> [1]\nparams[0]: 0.5 -> 0.75 (+0.25)"
+    )
+    assert gate_diff(CNOT(0, 1), CNOT(2, 1)) == "control_indices: [0] -> [2]"
+    assert gate_diff(U2(0, 0.5, 1.0), U1(0, 0.5)) == (
+        "name: U2 -> U1\nparams[1]: 1 -> removed"
+    )
+
+
+def test_to_coo() -> None:
+    rows, cols, values = CNOT(0, 1).to_coo()
+    assert (rows, cols) == ([0, 1, 2, 3], [0, 3, 2, 1])
+    assert values == [1.0, 1.0, 1.0, 1.0]
+    dense = np.zeros((4, 4), dtype=complex)
+    dense[rows, cols] = values
+    assert np.allclose(dense, CNOT(0, 1).matrix())
+    gate = UnitaryMatrix((0,), [[1, 1e-13], [0, 1j]])
+    assert gate.to_coo() == ([0, 1], [0, 1], [1, 1j])
+    assert gate.to_coo(atol=0.0) == ([0, 0, 1], [0, 1, 1], [1, 1e-13, 1j])
+    with pytest.raises(ValueError):
+        Measurement((0,), (0,)).to_coo()
+
+
+def test_disjoint_from() -> None:
+    assert CNOT(0, 1).disjoint_from(RX(2, 0.5))
+    assert not CNOT(0, 1).disjoint_from(RX(0, 0.5))
+    assert not RX(1, 0.5).disjoint_from(CNOT(0, 1))
+    assert not TOFFOLI(0, 1, 2).disjoint_from(Measurement((1,), (0,)))
+    assert Pauli((0, 1), (1, 3)).disjoint_from(SWAP(2, 3))
+
+
+def test_generator() -> None:
+    from quri_parts.circuit import CRX
+
+    assert np.allclose(RX(0, 0.5).generator(), [[0, 0.25], [0.25, 0]])
+    for gate in [
+        RY(0, 0.3),
+        U1(0, 0.7),
+        CRX(0, 1, 1.2),
+        PauliRotation((0, 2), (1, 2), 0.9),
+    ]:
+        w, v = np.linalg.eigh(np.array(gate.generator()))
+        expected = v @ np.diag(np.exp(-1j * w)) @ v.conj().T
+        assert np.allclose(expected, gate.matrix())
+    with pytest.raises(ValueError):
+        H(0).generator()
+
+
+def test_param_count_validation() -> None:
+    with pytest.raises(ValueError, match="RX gate takes 1 parameter, got 0"):
+        QuantumGate("RX", (0,))
+    with pytest.raises(ValueError, match="RX gate takes 1 parameter, got 2"):
+        QuantumGate("RX", (0,), params=(0.1, 0.2))
+    with pytest.raises(ValueError, match="U3 gate takes 3 parameters, got 1"):
+        QuantumGate("U3", (0,), params=(0.1,))
+    with pytest.raises(ValueError, match="Pauli gate takes 0 parameters"):
+        QuantumGate("Pauli", (0,), params=(0.1,), pauli_ids=(1,))
+    with pytest.raises(ValueError):
+        QuantumGate("CRX", (1,), (0,))
+    assert QuantumGate("RX", (0,), params=(0.1,)) == RX(0, 0.1)
+    assert QuantumGate("Custom", (0,), params=(0.1, 0.2)).kind == "Other"
+
+
+def test_basis_permutation() -> None:
+    # CNOT(0, 1) flips qubit 1, bit 1 of the index, when qubit 0 is set.
+    assert CNOT(0, 1).basis_permutation() == [0, 3, 2, 1]
+    assert SWAP(0, 1).basis_permutation() == [0, 2, 1, 3]
+    assert TOFFOLI(0, 1, 2).basis_permutation() == [0, 1, 2, 7, 4, 5, 6, 3]
+    assert X(0).basis_permutation() == [1, 0]
+    for gate in [CZ(0, 1), Y(0), H(0)]:
+        with pytest.raises(ValueError):
+            gate.basis_permutation()
+
+
+class _TargetOnlyRX(ParametricQuantumGate):
+    # A constructor signature unlike the base class, as subclasses often have.
+    def __new__(cls, target: int) -> "_TargetOnlyRX":
+        return super().__new__(cls, "ParametricRX", (target,), param_labels=["a"])
+
+    def __init__(self, target: int) -> None:
+        self.note = "rx"
+
+
+def test_parametric_subclass_pickle() -> None:
+    import pickle
+
+    gate = _TargetOnlyRX(2)
+    restored = pickle.loads(pickle.dumps(gate))
+    assert type(restored) is _TargetOnlyRX
+    assert restored == gate
+    assert restored.param_labels == ["a"]
+    assert restored.note == "rx"
+    plain = pickle.loads(pickle.dumps(ParametricRX(0)))
+    assert type(plain) is ParametricQuantumGate
+    assert plain == ParametricRX(0)
+
+
+def test_canonicalize() -> None:
+    assert CZ(1, 0) != CZ(0, 1)
+    assert CZ(1, 0).canonicalize() == CZ(0, 1)
+    assert CZ(1, 0).canonicalize().control_indices == (0,)
+    assert hash(CZ(1, 0).canonicalize()) == hash(CZ(0, 1))
+    assert SWAP(3, 1).canonicalize().target_indices == (1, 3)
+    assert TOFFOLI(5, 2, 0).canonicalize().control_indices == (2, 5)
+    rotation = PauliRotation((2, 0), (3, 1), 0.5).canonicalize()
+    assert rotation.target_indices == (0, 2)
+    assert rotation.pauli_ids == (1, 3)
+    measurement = Measurement((1, 0), (0, 1)).canonicalize()
+    assert measurement.classical_indices == (1, 0)
+    # CNOT is not symmetric, so its qubits stay where they are.
+    assert CNOT(1, 0).canonicalize() == CNOT(1, 0)
+
+
+def test_ecr() -> None:
+    from quri_parts.circuit import ECR
+
+    gate = ECR(0, 1)
+    assert gate == QuantumGate("ECR", target_indices=(0, 1))
+    assert gate.kind == "Clifford"
+    assert gate.is_clifford()
+    # Qiskit's ECR matrix, with qubit 0 as the lowest bit.
+    expected = np.array(
+        [[0, 1, 0, 1j], [1, 0, -1j, 0], [0, 1j, 0, 1], [-1j, 0, 1, 0]]
+    ) / np.sqrt(2)
+    assert np.allclose(gate.matrix(), expected)
+    assert gate.inverse() == gate
+    assert gate.is_inverse_of(gate)
+    assert gate.power(2) == Pauli((0, 1), (0, 0))
+    assert ECR(1, 0) != gate
+    assert QuantumGate.from_qiskit("ecr", (0, 1)) == gate
+    with pytest.raises(ValueError):
+        ECR(1, 1)
+    with pytest.raises(ValueError):
+        QuantumGate("ECR", target_indices=(2, 2))
+
+
+def test_is_parametric() -> None:
+    from quri_parts.circuit.gate import is_parametric
+
+    gates = [RX(0, 0.5), ParametricRX(0), _TargetOnlyRX(1), Measurement((0,), (0,))]
+    assert [is_parametric(g) for g in gates] == [False, True, True, False]
+    with pytest.raises(TypeError):
+        is_parametric("RX")  # type: ignore[arg-type]
+
+
+def test_uncontrol() -> None:
+    from quri_parts.circuit import CRY
+
+    assert CNOT(0, 1).uncontrol([0]) == X(1)
+    assert CZ(0, 1).uncontrol([0]) == Z(1)
+    assert CRY(2, 0, 0.5).uncontrol([2]) == RY(0, 0.5)
+    assert TOFFOLI(0, 1, 2).uncontrol([0]) == CNOT(1, 2)
+    assert TOFFOLI(0, 1, 2).uncontrol([1, 0]) == X(2)
+    assert TOFFOLI(0, 1, 2).uncontrol([]) == TOFFOLI(0, 1, 2)
+    custom = QuantumGate("Custom", (2,), (0, 1), params=(0.5,))
+    assert custom.uncontrol([1]) == QuantumGate("Custom", (2,), (0,), params=(0.5,))
+    with pytest.raises(ValueError):
+        CNOT(0, 1).uncontrol([1])
+    with pytest.raises(ValueError):
+        RX(0, 0.5).uncontrol([0])
+
+
+def test_matrices() -> None:
+    from quri_parts.circuit.gate import matrices
+
+    gates = [H(0), CNOT(0, 1), RZ(1, 0.5)]
+    assert matrices(gates) == [g.matrix() for g in gates]
+    assert matrices([]) == []
+    with_measurement = [X(0), Measurement((0,), (0,))]
+    with pytest.raises(ValueError):
+        matrices(with_measurement)
+    assert matrices(with_measurement, skip_missing=True) == [X(0).matrix(), None]
+
+
+def test_sort_key() -> None:
+    gates = [CNOT(2, 1), X(3), RX(0, 0.1), CNOT(0, 1), X(0), SWAP(4, 0)]
+    ordered = sorted(gates, key=QuantumGate.sort_key)
+    assert ordered == [CNOT(0, 1), CNOT(2, 1), RX(0, 0.1), SWAP(4, 0), X(0), X(3)]
+    assert sorted(reversed(gates), key=QuantumGate.sort_key) == ordered
+    assert SWAP(4, 0).sort_key() == ("SWAP", 0, (4, 0), ())
+    assert QuantumGate("Custom", ()).sort_key() == ("Custom", -1, (), ())
+
+
+def test_named_gate_global_phase() -> None:
+    assert S(0).global_phase() == pytest.approx(np.pi / 4)
+    assert Tdag(0).global_phase() == pytest.approx(-np.pi / 8)
+    assert U1(0, 0.6).global_phase() == pytest.approx(0.3)
+    # S = exp(i pi/4) RZ(pi/2).
+    phase = np.exp(1j * S(0).global_phase())
+    assert np.allclose(S(0).matrix(), phase * np.array(RZ(0, np.pi / 2).matrix()))
+    u3 = U3(0, 0.3, 0.5, 0.7)
+    assert u3.global_phase() == pytest.approx(0.6)
+    assert RX(0, 0.5).global_phase() == 0.0
+    assert CNOT(0, 1).global_phase() == 0.0
@@ -7,18 +7,24 @@ from typing_extensions import TypeAlias
 
 from quri_parts.circuit import (
     CNOT,
+    CRX,
+    CRY,
+    CRZ,
+    ISWAP,
     RX,
     RY,
     RZ,
     U1,
     U2,
     U3,
+    GlobalPhase,
     H,
     PauliRotation,
     QuantumCircuit,
     QuantumGate,
     S,
     Sdag,
+    SqrtISWAP,
     SqrtX,
     SqrtXdag,
     SqrtY,
@@ -129,6 +135,17 @@ def test_inverse_gate() -> None:
         PauliRotation(target_idx, pauli_ids, -angle),
     )
 
+    for cf in [CRX, CRY, CRZ]:
+        theta = np.random.rand()
+        _assert_inverse_gates(cf(3, 7, theta), cf(3, 7, -theta))
+
+    phase = np.random.rand()
+    _assert_inverse_gates(GlobalPhase(7, phase), GlobalPhase(7, -phase))
+
+    for g in [ISWAP(3, 7), SqrtISWAP(3, 7)]:
+        inverse_matrix = np.array(g.matrix()).conj().T
+        assert inverse_gate(g) == UnitaryMatrix((3, 7), inverse_matrix.tolist())
+
     # unitary
     unitary = unitary_group.rvs(2**4)
     _assert_inverse_gates(
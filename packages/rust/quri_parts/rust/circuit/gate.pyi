@@ -1,6 +1,24 @@
-from typing import Any, Optional, Sequence, Tuple
+from typing import Any, Hashable, Literal, Mapping, Optional, Sequence, Tuple, Union
+
+import numpy as np
+import numpy.typing as npt
+
+GateKind = Literal[
+    "Pauli",
+    "Clifford",
+    "NonClifford",
+    "Rotation",
+    "UnitaryMatrix",
+    "Measurement",
+    "Other",
+]
+
+class UnknownGateError(ValueError): ...
+class InvalidUnitaryError(ValueError): ...
+class IndexConflictError(ValueError): ...
 
 class QuantumGate:
+    __match_args__: Tuple[str, str, str, str]
     def __init__(
         self,
         name: str,
@@ -12,11 +30,111 @@ class QuantumGate:
         unitary_matrix: Optional[Sequence[Sequence[complex]]] = None,
     ) -> None: ...
     def __repr__(self) -> str: ...
+    def __str__(self) -> str: ...
+    def pretty(self) -> str: ...
+    def sort_key(self) -> Tuple[str, int, Tuple[int, ...], Tuple[int, ...]]: ...
     def __reduce__(self) -> Tuple[type, Any]: ...
+    def __copy__(self) -> "QuantumGate": ...
+    def __deepcopy__(self, memo: dict[int, Any]) -> "QuantumGate": ...
     def __hash__(self) -> int: ...
+    def isclose(
+        self, other: "QuantumGate", rtol: float = 1e-9, atol: float = 1e-12
+    ) -> bool: ...
+    def structurally_equal(self, other: "QuantumGate") -> bool: ...
+    def is_diagonal(self, atol: float = 1e-10) -> bool: ...
+    def is_identity(
+        self, atol: float = 1e-10, up_to_global_phase: bool = True
+    ) -> bool: ...
+    def trace(self) -> complex: ...
+    def determinant(self) -> complex: ...
+    def nnz(self, atol: float = 1e-12) -> int: ...
+    def to_coo(
+        self, atol: float = 1e-12
+    ) -> Tuple[list[int], list[int], list[complex]]: ...
+    def basis_permutation(self) -> list[int]: ...
+    def is_permutation(self, atol: float = 1e-10) -> bool: ...
+    def process_fidelity(self, other: "QuantumGate") -> float: ...
+    def qubits(self) -> Tuple[int, ...]: ...
+    def locality(self) -> int: ...
+    def disjoint_from(self, other: "QuantumGate") -> bool: ...
+    def is_inverse_of(self, other: "QuantumGate", atol: float = 1e-10) -> bool: ...
+    def commutes_with(self, other: "QuantumGate", atol: float = 1e-10) -> bool: ...
+    def is_clifford(self) -> bool: ...
+    def is_measurement(self) -> bool: ...
+    def diagonalizing_circuit(self) -> list["QuantumGate"]: ...
+    def measurement_pairs(self) -> list[Tuple[int, int]]: ...
+    def propagate_pauli(
+        self, pauli_ids: Sequence[int], qubits: Sequence[int]
+    ) -> Tuple[Sequence[int], Sequence[int], int]: ...
+    def equiv_up_to_global_phase(
+        self, other: "QuantumGate", atol: float = 1e-10
+    ) -> bool: ...
+    def is_unitary(self, atol: float = 1e-10) -> bool: ...
+    def controlled(self, control_index: int) -> "QuantumGate": ...
+    def uncontrol(self, assume_control_set: Sequence[int]) -> "QuantumGate": ...
+    def inverse(self) -> "QuantumGate": ...
+    def power(self, n: int) -> "QuantumGate": ...
+    def pauli_string(self, strict: bool = False) -> str: ...
+    def angle(self) -> float: ...
+    def angle_degrees(self) -> float: ...
+    def with_duration(self, ns: float) -> "QuantumGate": ...
+    def normalized(self) -> "QuantumGate": ...
+    def global_phase(self) -> float: ...
+    def canonicalize(self) -> "QuantumGate": ...
+    def scale_params(self, factor: float) -> "QuantumGate": ...
+    def as_parametric(self) -> "ParametricQuantumGate": ...
+    def remap_qubits(
+        self,
+        mapping: Mapping[int, int],
+        classical_mapping: Optional[Mapping[int, int]] = None,
+        strict: bool = False,
+    ) -> "QuantumGate": ...
+    def decompose(self) -> Sequence["QuantumGate"]: ...
+    def matrix(
+        self, endian: Literal["big", "little"] = "little"
+    ) -> Sequence[Sequence[complex]]: ...
+    def expected_matrix(self) -> Sequence[Sequence[complex]]: ...
+    def generator(self) -> Sequence[Sequence[complex]]: ...
+    def with_swapped_order(self) -> "QuantumGate": ...
+    def to_matrix_gate(self) -> "QuantumGate": ...
+    def to_qasm(self) -> str: ...
+    def to_qasm3(self) -> str: ...
+    def to_cirq_spec(self) -> Tuple[str, Sequence[int], Sequence[float]]: ...
+    def to_stim(self) -> str: ...
+    @classmethod
+    def from_qasm(cls, line: str) -> "QuantumGate": ...
+    @classmethod
+    def from_qiskit(
+        cls, name: str, qubits: Sequence[int], params: Sequence[float] = ()
+    ) -> "QuantumGate": ...
+    def canonical_repr(self) -> str: ...
+    def content_hash(self) -> int: ...
+    @classmethod
+    def unitary_from_buffer(
+        cls, target_indices: Sequence[int], flat: Sequence[complex], dim: int
+    ) -> "QuantumGate": ...
+    def as_dict(self) -> dict[str, Any]: ...
+    @classmethod
+    def from_dict(cls, d: dict[str, Any]) -> "QuantumGate": ...
+    def to_bytes(self) -> bytes: ...
+    @classmethod
+    def from_bytes(cls, data: bytes) -> "QuantumGate": ...
+    def to_json(self) -> str: ...
+    @classmethod
+    def from_json(cls, s: str) -> "QuantumGate": ...
     @property
     def name(self) -> str: ...
     @property
+    def kind(self) -> GateKind: ...
+    @property
+    def matrix_array(self) -> Optional["npt.NDArray[np.complex128]"]: ...
+    @property
+    def classical_bit_count(self) -> int: ...
+    @property
+    def qubit_count(self) -> int: ...
+    @property
+    def duration(self) -> Optional[float]: ...
+    @property
     def target_indices(self) -> Sequence[int]: ...
     @property
     def control_indices(self) -> Sequence[int]: ...
@@ -36,15 +154,58 @@ class ParametricQuantumGate:
         target_indices: Sequence[int],
         control_indices: Sequence[int] = [],
         pauli_ids: Sequence[int] = [],
+        param_labels: Optional[Sequence[str]] = None,
     ) -> None: ...
     def __repr__(self) -> str: ...
     def __reduce__(self) -> Tuple[type, Any]: ...
+    def bind(self, values: Sequence[float]) -> QuantumGate: ...
+    def substitute(
+        self, param_map: Mapping[Hashable, float], keys: Sequence[Hashable]
+    ) -> QuantumGate: ...
+    def shift_rule(self) -> list[Tuple[float, float]]: ...
+    def to_qasm3(self, param_name: str) -> str: ...
+    def __copy__(self) -> "ParametricQuantumGate": ...
+    def __deepcopy__(self, memo: dict[int, Any]) -> "ParametricQuantumGate": ...
     def __hash__(self) -> int: ...
     @property
     def name(self) -> str: ...
     @property
+    def kind(self) -> Literal["Parametric"]: ...
+    @property
+    def parameter_count(self) -> int: ...
+    @property
+    def param_labels(self) -> Optional[Sequence[str]]: ...
+    @property
     def target_indices(self) -> Sequence[int]: ...
     @property
     def control_indices(self) -> Sequence[int]: ...
     @property
     def pauli_ids(self) -> Sequence[int]: ...
+
+def fuse_single_qubit(gates: Sequence[QuantumGate]) -> QuantumGate: ...
+def tensor(gate_a: QuantumGate, gate_b: QuantumGate) -> QuantumGate: ...
+def matrices(
+    gates: Sequence[QuantumGate], skip_missing: bool = False
+) -> list[Optional[Sequence[Sequence[complex]]]]: ...
+def u1(target: int, lam: float) -> QuantumGate: ...
+def u2(target: int, phi: float, lam: float) -> QuantumGate: ...
+def u3(target: int, theta: float, phi: float, lam: float) -> QuantumGate: ...
+def apply_gate(
+    state: "npt.NDArray[np.complex128]", gate: QuantumGate, num_qubits: int
+) -> None: ...
+def gates_from_arrays(
+    names: Sequence[str],
+    target_indices_list: Sequence[Sequence[int]],
+    params_list: Optional[Sequence[Sequence[float]]] = None,
+    control_indices_list: Optional[Sequence[Sequence[int]]] = None,
+    classical_indices_list: Optional[Sequence[Sequence[int]]] = None,
+    pauli_ids_list: Optional[Sequence[Sequence[int]]] = None,
+) -> list[QuantumGate]: ...
+def is_parametric(gate: Union[QuantumGate, ParametricQuantumGate]) -> bool: ...
+def count_by_name(gates: Sequence[QuantumGate]) -> dict[str, int]: ...
+def t_count(gates: Sequence[QuantumGate]) -> int: ...
+def two_qubit_count(gates: Sequence[QuantumGate]) -> int: ...
+def inverse_sequence(gates: Sequence[QuantumGate]) -> list[QuantumGate]: ...
+def supported_gates() -> list[dict[str, Any]]: ...
+def merge_rotations(g1: QuantumGate, g2: QuantumGate) -> Optional[QuantumGate]: ...
+def gate_diff(before: QuantumGate, after: QuantumGate) -> Optional[str]: ...
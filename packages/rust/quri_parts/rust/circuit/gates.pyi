@@ -18,12 +18,22 @@ def Tdag(target_index: int) -> QuantumGate: ...
 def RX(target_index: int, angle: float) -> QuantumGate: ...
 def RY(target_index: int, angle: float) -> QuantumGate: ...
 def RZ(target_index: int, angle: float) -> QuantumGate: ...
+def RX_deg(target_index: int, degrees: float) -> QuantumGate: ...
+def RY_deg(target_index: int, degrees: float) -> QuantumGate: ...
+def RZ_deg(target_index: int, degrees: float) -> QuantumGate: ...
 def U1(target_index: int, lmd: float) -> QuantumGate: ...
 def U2(target_index: int, phi: float, lmd: float) -> QuantumGate: ...
 def U3(target_index: int, theta: float, phi: float, lmd: float) -> QuantumGate: ...
+def GlobalPhase(target_index: int, phase: float) -> QuantumGate: ...
 def CNOT(control_index: int, target_index: int) -> QuantumGate: ...
 def CZ(control_index: int, target_index: int) -> QuantumGate: ...
+def CRX(control_index: int, target_index: int, angle: float) -> QuantumGate: ...
+def CRY(control_index: int, target_index: int, angle: float) -> QuantumGate: ...
+def CRZ(control_index: int, target_index: int, angle: float) -> QuantumGate: ...
 def SWAP(target_index1: int, target_index2: int) -> QuantumGate: ...
+def ISWAP(target_index1: int, target_index2: int) -> QuantumGate: ...
+def SqrtISWAP(target_index1: int, target_index2: int) -> QuantumGate: ...
+def ECR(target_index1: int, target_index2: int) -> QuantumGate: ...
 def TOFFOLI(
     control_index1: int, control_index2: int, target_index: int
 ) -> QuantumGate: ...
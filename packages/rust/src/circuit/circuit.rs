@@ -396,7 +396,11 @@ warnings.warn(
         control_index: usize,
         target_index: usize,
     ) -> PyResult<()> {
-        Self::add_gate(slf, QuantumGate::CNOT(control_index, target_index), None)
+        Self::add_gate(
+            slf,
+            crate::circuit::gates::cnot(control_index, target_index)?,
+            None,
+        )
     }
 
     #[allow(non_snake_case)]
@@ -405,7 +409,11 @@ warnings.warn(
         control_index: usize,
         target_index: usize,
     ) -> PyResult<()> {
-        Self::add_gate(slf, QuantumGate::CZ(control_index, target_index), None)
+        Self::add_gate(
+            slf,
+            crate::circuit::gates::cz(control_index, target_index)?,
+            None,
+        )
     }
 
     #[allow(non_snake_case)]
@@ -414,7 +422,11 @@ warnings.warn(
         target_index1: usize,
         target_index2: usize,
     ) -> PyResult<()> {
-        Self::add_gate(slf, QuantumGate::SWAP(target_index1, target_index2), None)
+        Self::add_gate(
+            slf,
+            crate::circuit::gates::swap(target_index1, target_index2)?,
+            None,
+        )
     }
 
     #[allow(non_snake_case)]
@@ -426,7 +438,7 @@ warnings.warn(
     ) -> PyResult<()> {
         Self::add_gate(
             slf,
-            QuantumGate::TOFFOLI(control_index1, control_index2, target_index),
+            crate::circuit::gates::toffoli(control_index1, control_index2, target_index)?,
             None,
         )
     }
@@ -483,7 +495,7 @@ warnings.warn(
     ) -> PyResult<()> {
         Self::add_gate(
             slf,
-            crate::circuit::gates::pauli(target_indices, pauli_ids),
+            crate::circuit::gates::pauli(target_indices, pauli_ids)?,
             None,
         )
     }
@@ -497,7 +509,7 @@ warnings.warn(
     ) -> PyResult<()> {
         Self::add_gate(
             slf,
-            crate::circuit::gates::pauli_rotation(target_qubits, pauli_id_list, angle),
+            crate::circuit::gates::pauli_rotation(target_qubits, pauli_id_list, angle)?,
             None,
         )
     }
@@ -91,11 +91,18 @@ impl ImmutableParametricQuantumCircuit {
                         })?,
                     ),
                     QuantumGate::U1(q1, p1) => QuantumGate::U1(*q1, *p1),
+                    QuantumGate::GlobalPhase(q1, p1) => QuantumGate::GlobalPhase(*q1, *p1),
                     QuantumGate::U2(q1, p1, p2) => QuantumGate::U2(*q1, *p1, *p2),
                     QuantumGate::U3(q1, p1, p2, p3) => QuantumGate::U3(*q1, *p1, *p2, *p3),
                     QuantumGate::CNOT(q1, q2) => QuantumGate::CNOT(*q1, *q2),
+                    QuantumGate::CRX(q1, q2, p) => QuantumGate::CRX(*q1, *q2, *p),
+                    QuantumGate::CRY(q1, q2, p) => QuantumGate::CRY(*q1, *q2, *p),
+                    QuantumGate::CRZ(q1, q2, p) => QuantumGate::CRZ(*q1, *q2, *p),
                     QuantumGate::CZ(q1, q2) => QuantumGate::CZ(*q1, *q2),
                     QuantumGate::SWAP(q1, q2) => QuantumGate::SWAP(*q1, *q2),
+                    QuantumGate::ISWAP(q1, q2) => QuantumGate::ISWAP(*q1, *q2),
+                    QuantumGate::SqrtISWAP(q1, q2) => QuantumGate::SqrtISWAP(*q1, *q2),
+                    QuantumGate::ECR(q1, q2) => QuantumGate::ECR(*q1, *q2),
                     QuantumGate::TOFFOLI(q1, q2, q3) => QuantumGate::TOFFOLI(*q1, *q2, *q3),
                     QuantumGate::UnitaryMatrix(q1, mat) => {
                         QuantumGate::UnitaryMatrix(q1.clone(), mat.clone())
@@ -638,7 +645,11 @@ impl ParametricQuantumCircuit {
         control_index: usize,
         target_index: usize,
     ) -> PyResult<()> {
-        Self::add_gate(slf, QuantumGate::CNOT(control_index, target_index), None)
+        Self::add_gate(
+            slf,
+            crate::circuit::gates::cnot(control_index, target_index)?,
+            None,
+        )
     }
 
     #[allow(non_snake_case)]
@@ -647,7 +658,11 @@ impl ParametricQuantumCircuit {
         control_index: usize,
         target_index: usize,
     ) -> PyResult<()> {
-        Self::add_gate(slf, QuantumGate::CZ(control_index, target_index), None)
+        Self::add_gate(
+            slf,
+            crate::circuit::gates::cz(control_index, target_index)?,
+            None,
+        )
     }
 
     #[allow(non_snake_case)]
@@ -656,7 +671,11 @@ impl ParametricQuantumCircuit {
         target_index1: usize,
         target_index2: usize,
     ) -> PyResult<()> {
-        Self::add_gate(slf, QuantumGate::SWAP(target_index1, target_index2), None)
+        Self::add_gate(
+            slf,
+            crate::circuit::gates::swap(target_index1, target_index2)?,
+            None,
+        )
     }
 
     #[allow(non_snake_case)]
@@ -668,7 +687,7 @@ impl ParametricQuantumCircuit {
     ) -> PyResult<()> {
         Self::add_gate(
             slf,
-            QuantumGate::TOFFOLI(control_index1, control_index2, target_index),
+            crate::circuit::gates::toffoli(control_index1, control_index2, target_index)?,
             None,
         )
     }
@@ -725,7 +744,7 @@ impl ParametricQuantumCircuit {
     ) -> PyResult<()> {
         Self::add_gate(
             slf,
-            crate::circuit::gates::pauli(target_indices, pauli_ids),
+            crate::circuit::gates::pauli(target_indices, pauli_ids)?,
             None,
         )
     }
@@ -1,6 +1,24 @@
 use num_complex::Complex64;
 use pyo3::prelude::*;
-use pyo3::types::PyTuple;
+use pyo3::types::{PyBytes, PyDict, PyTuple, PyType};
+use std::collections::HashMap;
+
+pub mod binary;
+pub mod cirq;
+pub mod clifford;
+pub mod decompose;
+pub mod diff;
+pub mod error;
+pub mod json;
+pub mod matrix;
+pub mod merge;
+pub mod qasm;
+pub mod resource;
+pub mod schema;
+pub mod statevector;
+pub mod stim;
+
+pub use error::{IndexConflictError, InvalidUnitaryError, UnknownGateError};
 
 #[derive(Clone, Debug, PartialEq)]
 #[repr(C)]
@@ -24,9 +42,16 @@ pub enum QuantumGate<P = f64> {
     U1(usize, f64),
     U2(usize, f64, f64),
     U3(usize, f64, f64, f64),
+    GlobalPhase(usize, f64),
     CNOT(usize, usize),
     CZ(usize, usize),
+    CRX(usize, usize, f64),
+    CRY(usize, usize, f64),
+    CRZ(usize, usize, f64),
     SWAP(usize, usize),
+    ISWAP(usize, usize),
+    SqrtISWAP(usize, usize),
+    ECR(usize, usize),
     TOFFOLI(usize, usize, usize),
     UnitaryMatrix(Vec<usize>, Vec<Vec<Complex64>>),
     Pauli(Vec<usize>, Vec<u8>),
@@ -35,6 +60,16 @@ pub enum QuantumGate<P = f64> {
     Other(Box<GenericGateProperty>),
 }
 
+impl Eq for QuantumGate<f64> {}
+
+/// Hashes the gate through its property, whose equality is coarser than the
+/// derived `PartialEq`, so equal gates always hash equal.
+impl std::hash::Hash for QuantumGate<f64> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.clone().into_property().hash(state)
+    }
+}
+
 impl<P: Clone> QuantumGate<P> {
     pub fn get_qubits(&self) -> Vec<usize> {
         match self {
@@ -55,10 +90,19 @@ impl<P: Clone> QuantumGate<P> {
             | Self::RY(q, _)
             | Self::RZ(q, _)
             | Self::U1(q, _)
+            | Self::GlobalPhase(q, _)
             | Self::U2(q, _, _)
             | Self::U3(q, _, _, _) => vec![*q],
             Self::Measurement(qs, _) => qs.clone().into(),
-            Self::CNOT(q0, q1) | Self::CZ(q0, q1) | Self::SWAP(q0, q1) => vec![*q0, *q1],
+            Self::CNOT(q0, q1)
+            | Self::CZ(q0, q1)
+            | Self::SWAP(q0, q1)
+            | Self::ISWAP(q0, q1)
+            | Self::SqrtISWAP(q0, q1)
+            | Self::ECR(q0, q1)
+            | Self::CRX(q0, q1, _)
+            | Self::CRY(q0, q1, _)
+            | Self::CRZ(q0, q1, _) => vec![*q0, *q1],
             Self::TOFFOLI(q0, q1, q2) => vec![*q0, *q1, *q2],
             Self::UnitaryMatrix(qs, _) | Self::Pauli(qs, _) | Self::PauliRotation(qs, _, _) => {
                 qs.clone().into()
@@ -71,6 +115,40 @@ impl<P: Clone> QuantumGate<P> {
         }
     }
 
+    /// The qubits acted on by the gate in ascending order, without duplicates.
+    pub fn sorted_qubits(&self) -> Vec<usize> {
+        let mut qubits = self.get_qubits();
+        qubits.sort_unstable();
+        qubits.dedup();
+        qubits
+    }
+
+    /// Number of qubits the gate acts on non-trivially: the non-identity Pauli ids
+    /// of a Pauli gate, and `sorted_qubits().len()` for every other gate.
+    pub fn locality(&self) -> usize {
+        match self {
+            Self::Pauli(qs, ps) => {
+                let mut qubits: Vec<usize> = qs
+                    .iter()
+                    .zip(ps)
+                    .filter(|(_, p)| **p != 0)
+                    .map(|(q, _)| *q)
+                    .collect();
+                qubits.sort_unstable();
+                qubits.dedup();
+                qubits.len()
+            }
+            _ => self.sorted_qubits().len(),
+        }
+    }
+
+    /// Whether the two gates share no target or control qubit, so they can run in
+    /// the same layer.
+    pub fn disjoint_from(&self, other: &Self) -> bool {
+        let qubits = self.get_qubits();
+        other.get_qubits().iter().all(|q| !qubits.contains(q))
+    }
+
     pub fn get_cbits(&self) -> Vec<usize> {
         match self {
             Self::Measurement(_, cs) => cs.clone().into(),
@@ -87,6 +165,43 @@ impl<P: Clone> QuantumGate<P> {
         }
     }
 
+    /// Coarse category of the gate: `"Pauli"`, `"Clifford"`, `"NonClifford"`,
+    /// `"Rotation"`, `"UnitaryMatrix"`, `"Measurement"` or `"Other"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Identity(_) | Self::X(_) | Self::Y(_) | Self::Z(_) | Self::Pauli(_, _) => "Pauli",
+            Self::H(_)
+            | Self::S(_)
+            | Self::Sdag(_)
+            | Self::SqrtX(_)
+            | Self::SqrtXdag(_)
+            | Self::SqrtY(_)
+            | Self::SqrtYdag(_)
+            | Self::CNOT(_, _)
+            | Self::CZ(_, _)
+            | Self::SWAP(_, _)
+            | Self::ISWAP(_, _)
+            | Self::ECR(_, _) => "Clifford",
+            Self::T(_) | Self::Tdag(_) | Self::SqrtISWAP(_, _) | Self::TOFFOLI(_, _, _) => {
+                "NonClifford"
+            }
+            Self::RX(_, _)
+            | Self::RY(_, _)
+            | Self::RZ(_, _)
+            | Self::U1(_, _)
+            | Self::U2(_, _, _)
+            | Self::U3(_, _, _, _)
+            | Self::GlobalPhase(_, _)
+            | Self::CRX(_, _, _)
+            | Self::CRY(_, _, _)
+            | Self::CRZ(_, _, _)
+            | Self::PauliRotation(_, _, _) => "Rotation",
+            Self::UnitaryMatrix(_, _) => "UnitaryMatrix",
+            Self::Measurement(_, _) => "Measurement",
+            Self::Other(_) => "Other",
+        }
+    }
+
     pub fn map_param<Q>(self, mut f: impl FnMut(P) -> Q) -> QuantumGate<Q> {
         match self {
             QuantumGate::Identity(q) => QuantumGate::Identity(q),
@@ -106,11 +221,18 @@ impl<P: Clone> QuantumGate<P> {
             QuantumGate::RY(q, p) => QuantumGate::RY(q, f(p)),
             QuantumGate::RZ(q, p) => QuantumGate::RZ(q, f(p)),
             QuantumGate::U1(q, p) => QuantumGate::U1(q, p),
+            QuantumGate::GlobalPhase(q, p) => QuantumGate::GlobalPhase(q, p),
             QuantumGate::U2(q, p0, p1) => QuantumGate::U2(q, p0, p1),
             QuantumGate::U3(q, p0, p1, p2) => QuantumGate::U3(q, p0, p1, p2),
             QuantumGate::CNOT(q0, q1) => QuantumGate::CNOT(q0, q1),
+            QuantumGate::CRX(q0, q1, p) => QuantumGate::CRX(q0, q1, p),
+            QuantumGate::CRY(q0, q1, p) => QuantumGate::CRY(q0, q1, p),
+            QuantumGate::CRZ(q0, q1, p) => QuantumGate::CRZ(q0, q1, p),
             QuantumGate::CZ(q0, q1) => QuantumGate::CZ(q0, q1),
             QuantumGate::SWAP(q0, q1) => QuantumGate::SWAP(q0, q1),
+            QuantumGate::ISWAP(q0, q1) => QuantumGate::ISWAP(q0, q1),
+            QuantumGate::SqrtISWAP(q0, q1) => QuantumGate::SqrtISWAP(q0, q1),
+            QuantumGate::ECR(q0, q1) => QuantumGate::ECR(q0, q1),
             QuantumGate::TOFFOLI(q0, q1, q2) => QuantumGate::TOFFOLI(q0, q1, q2),
             QuantumGate::UnitaryMatrix(qs, mat) => QuantumGate::UnitaryMatrix(qs, mat),
             QuantumGate::Pauli(qs, ps) => QuantumGate::Pauli(qs, ps),
@@ -122,182 +244,60 @@ impl<P: Clone> QuantumGate<P> {
 }
 
 impl QuantumGate<f64> {
+    /// The named variant for `prop` if it matches one of
+    /// `schema::GATE_SIGNATURES`, `None` for parametric gate names, and `Other`
+    /// for anything else.
     pub fn from_property(prop: GenericGateProperty) -> PyResult<Option<Self>> {
-        let is_nonpara_named_1q_gate = || {
-            prop.target_indices.len() == 1
-                && prop.control_indices.is_empty()
-                && prop.classical_indices.is_empty()
-                && prop.params.is_empty()
-                && prop.pauli_ids.is_empty()
-                && prop.unitary_matrix.is_none()
-        };
-
-        let is_para_1q_gate = || {
-            prop.target_indices.len() == 1
-                && prop.control_indices.is_empty()
-                && prop.classical_indices.is_empty()
-                && prop.params.len() == 1
-                && prop.pauli_ids.is_empty()
-                && prop.unitary_matrix.is_none()
-        };
-
-        match prop.name.as_str() {
-            "Identity" if is_nonpara_named_1q_gate() => {
-                Ok(Some(Self::Identity(prop.target_indices[0])))
-            }
-            "X" if is_nonpara_named_1q_gate() => Ok(Some(Self::X(prop.target_indices[0]))),
-            "Y" if is_nonpara_named_1q_gate() => Ok(Some(Self::Y(prop.target_indices[0]))),
-            "Z" if is_nonpara_named_1q_gate() => Ok(Some(Self::Z(prop.target_indices[0]))),
-            "H" if is_nonpara_named_1q_gate() => Ok(Some(Self::H(prop.target_indices[0]))),
-            "S" if is_nonpara_named_1q_gate() => Ok(Some(Self::S(prop.target_indices[0]))),
-            "Sdag" if is_nonpara_named_1q_gate() => Ok(Some(Self::Sdag(prop.target_indices[0]))),
-            "SqrtX" if is_nonpara_named_1q_gate() => Ok(Some(Self::SqrtX(prop.target_indices[0]))),
-            "SqrtXdag" if is_nonpara_named_1q_gate() => {
-                Ok(Some(Self::SqrtXdag(prop.target_indices[0])))
-            }
-            "SqrtY" if is_nonpara_named_1q_gate() => Ok(Some(Self::SqrtY(prop.target_indices[0]))),
-            "SqrtYdag" if is_nonpara_named_1q_gate() => {
-                Ok(Some(Self::SqrtYdag(prop.target_indices[0])))
-            }
-            "T" if is_nonpara_named_1q_gate() => Ok(Some(Self::T(prop.target_indices[0]))),
-            "Tdag" if is_nonpara_named_1q_gate() => Ok(Some(Self::Tdag(prop.target_indices[0]))),
-            "RX" if is_para_1q_gate() => Ok(Some(Self::RX(prop.target_indices[0], prop.params[0]))),
-            "RY" if is_para_1q_gate() => Ok(Some(Self::RY(prop.target_indices[0], prop.params[0]))),
-            "RZ" if is_para_1q_gate() => Ok(Some(Self::RZ(prop.target_indices[0], prop.params[0]))),
-            "U1" if is_para_1q_gate() => Ok(Some(Self::U1(prop.target_indices[0], prop.params[0]))),
-            "U2" if prop.control_indices.is_empty()
-                && prop.target_indices.len() == 1
-                && prop.classical_indices.is_empty()
-                && prop.params.len() == 2
-                && prop.pauli_ids.is_empty()
-                && prop.unitary_matrix.is_none() =>
-            {
-                Ok(Some(Self::U2(
-                    prop.target_indices[0],
-                    prop.params[0],
-                    prop.params[1],
-                )))
-            }
-            "U3" if prop.control_indices.is_empty()
-                && prop.target_indices.len() == 1
-                && prop.classical_indices.is_empty()
-                && prop.params.len() == 3
-                && prop.pauli_ids.is_empty()
-                && prop.unitary_matrix.is_none() =>
-            {
-                Ok(Some(Self::U3(
-                    prop.target_indices[0],
-                    prop.params[0],
-                    prop.params[1],
-                    prop.params[2],
-                )))
-            }
-            "CNOT"
-                if prop.control_indices.len() == 1
-                    && prop.target_indices.len() == 1
-                    && prop.classical_indices.is_empty()
-                    && prop.params.is_empty()
-                    && prop.pauli_ids.is_empty()
-                    && prop.unitary_matrix.is_none() =>
-            {
-                Ok(Some(Self::CNOT(
-                    prop.control_indices[0],
-                    prop.target_indices[0],
-                )))
-            }
-            "CZ" if prop.control_indices.len() == 1
-                && prop.target_indices.len() == 1
-                && prop.classical_indices.is_empty()
-                && prop.params.is_empty()
-                && prop.pauli_ids.is_empty()
-                && prop.unitary_matrix.is_none() =>
-            {
-                Ok(Some(Self::CZ(
-                    prop.control_indices[0],
-                    prop.target_indices[0],
-                )))
-            }
-            "SWAP"
-                if prop.control_indices.is_empty()
-                    && prop.target_indices.len() == 2
-                    && prop.classical_indices.is_empty()
-                    && prop.params.is_empty()
-                    && prop.pauli_ids.is_empty()
-                    && prop.unitary_matrix.is_none() =>
-            {
-                Ok(Some(Self::SWAP(
-                    prop.target_indices[0],
-                    prop.target_indices[1],
-                )))
-            }
-            "TOFFOLI"
-                if prop.control_indices.len() == 2
-                    && prop.target_indices.len() == 1
-                    && prop.classical_indices.is_empty()
-                    && prop.params.is_empty()
-                    && prop.pauli_ids.is_empty()
-                    && prop.unitary_matrix.is_none() =>
-            {
-                Ok(Some(Self::TOFFOLI(
-                    prop.control_indices[0],
-                    prop.control_indices[1],
-                    prop.target_indices[0],
-                )))
-            }
-            "UnitaryMatrix"
-                if prop.control_indices.is_empty()
-                    && prop.classical_indices.is_empty()
-                    && prop.params.is_empty()
-                    && prop.pauli_ids.is_empty()
-                    && prop.unitary_matrix.is_some() =>
-            {
-                Ok(Some(crate::circuit::gates::unitary_matrix(
-                    prop.target_indices.into(),
-                    prop.unitary_matrix
-                        .unwrap()
-                        .into_iter()
-                        .map(|v| v.into())
-                        .collect(),
-                )?))
-            }
-            "Pauli"
-                if prop.control_indices.is_empty()
-                    && prop.classical_indices.is_empty()
-                    && prop.params.is_empty()
-                    && prop.pauli_ids.len() == prop.target_indices.len()
-                    && prop.unitary_matrix.is_none() =>
-            {
-                Ok(Some(Self::Pauli(prop.target_indices, prop.pauli_ids)))
-            }
-            "PauliRotation"
-                if prop.control_indices.is_empty()
-                    && prop.classical_indices.is_empty()
-                    && prop.params.len() == 1
-                    && prop.pauli_ids.len() == prop.target_indices.len()
-                    && prop.unitary_matrix.is_none() =>
-            {
-                Ok(Some(Self::PauliRotation(
-                    prop.target_indices,
-                    prop.pauli_ids,
-                    prop.params[0],
-                )))
-            }
-            "ParametricRX" | "ParametricRY" | "ParametricRZ" => Ok(None),
-            "Measurement"
-                if prop.control_indices.is_empty()
-                    && prop.target_indices.len() == prop.classical_indices.len()
-                    && prop.params.is_empty()
-                    && prop.pauli_ids.is_empty()
-                    && prop.unitary_matrix.is_none() =>
-            {
-                Ok(Some(Self::Measurement(
-                    prop.target_indices,
-                    prop.classical_indices,
-                )))
-            }
-
-            _ => Ok(Some(Self::Other(Box::new(prop)))),
+        if matches!(
+            prop.name.as_str(),
+            "ParametricRX" | "ParametricRY" | "ParametricRZ"
+        ) {
+            return Ok(None);
+        }
+        if schema::find_signature(&prop).is_none() {
+            return Ok(Some(Self::Other(Box::new(prop))));
         }
+        let (t, c, p) = (&prop.target_indices, &prop.control_indices, &prop.params);
+        Ok(Some(match prop.name.as_str() {
+            "Identity" => Self::Identity(t[0]),
+            "X" => Self::X(t[0]),
+            "Y" => Self::Y(t[0]),
+            "Z" => Self::Z(t[0]),
+            "H" => Self::H(t[0]),
+            "S" => Self::S(t[0]),
+            "Sdag" => Self::Sdag(t[0]),
+            "SqrtX" => Self::SqrtX(t[0]),
+            "SqrtXdag" => Self::SqrtXdag(t[0]),
+            "SqrtY" => Self::SqrtY(t[0]),
+            "SqrtYdag" => Self::SqrtYdag(t[0]),
+            "T" => Self::T(t[0]),
+            "Tdag" => Self::Tdag(t[0]),
+            "RX" => Self::RX(t[0], p[0]),
+            "RY" => Self::RY(t[0], p[0]),
+            "RZ" => Self::RZ(t[0], p[0]),
+            "U1" => Self::U1(t[0], p[0]),
+            "U2" => Self::U2(t[0], p[0], p[1]),
+            "U3" => Self::U3(t[0], p[0], p[1], p[2]),
+            "GlobalPhase" => Self::GlobalPhase(t[0], p[0]),
+            "CNOT" => Self::CNOT(c[0], t[0]),
+            "CZ" => Self::CZ(c[0], t[0]),
+            "CRX" => Self::CRX(c[0], t[0], p[0]),
+            "CRY" => Self::CRY(c[0], t[0], p[0]),
+            "CRZ" => Self::CRZ(c[0], t[0], p[0]),
+            "SWAP" => Self::SWAP(t[0], t[1]),
+            "ISWAP" => Self::ISWAP(t[0], t[1]),
+            "SqrtISWAP" => Self::SqrtISWAP(t[0], t[1]),
+            "ECR" => Self::ECR(t[0], t[1]),
+            "TOFFOLI" => Self::TOFFOLI(c[0], c[1], t[0]),
+            "UnitaryMatrix" => crate::circuit::gates::unitary_matrix(
+                prop.target_indices,
+                prop.unitary_matrix.unwrap(),
+            )?,
+            "Pauli" => Self::Pauli(prop.target_indices, prop.pauli_ids),
+            "PauliRotation" => Self::PauliRotation(prop.target_indices, prop.pauli_ids, p[0]),
+            "Measurement" => Self::Measurement(prop.target_indices, prop.classical_indices),
+            name => unreachable!("{} gate has a signature but no variant", name),
+        }))
     }
 
     pub fn into_property(self) -> GenericGateProperty {
@@ -343,6 +343,7 @@ impl QuantumGate<f64> {
             Self::RY(q, p) => para_1q_gate("RY", q, p),
             Self::RZ(q, p) => para_1q_gate("RZ", q, p),
             Self::U1(q, p) => para_1q_gate("U1", q, p),
+            Self::GlobalPhase(q, p) => para_1q_gate("GlobalPhase", q, p),
             Self::U2(q, p0, p1) => GenericGateProperty {
                 name: "U2".to_owned().into(),
                 target_indices: vec![q].into(),
@@ -367,11 +368,39 @@ impl QuantumGate<f64> {
                 target_indices: vec![q1].into(),
                 ..Default::default()
             },
+            Self::CRX(c, t, p) | Self::CRY(c, t, p) | Self::CRZ(c, t, p) => GenericGateProperty {
+                name: match self {
+                    Self::CRX(_, _, _) => "CRX",
+                    Self::CRY(_, _, _) => "CRY",
+                    _ => "CRZ",
+                }
+                .to_owned()
+                .into(),
+                control_indices: vec![c].into(),
+                target_indices: vec![t].into(),
+                params: vec![p].into(),
+                ..Default::default()
+            },
             Self::SWAP(q0, q1) => GenericGateProperty {
                 name: "SWAP".to_owned().into(),
                 target_indices: vec![q0, q1].into(),
                 ..Default::default()
             },
+            Self::ISWAP(q0, q1) => GenericGateProperty {
+                name: "ISWAP".to_owned().into(),
+                target_indices: vec![q0, q1].into(),
+                ..Default::default()
+            },
+            Self::SqrtISWAP(q0, q1) => GenericGateProperty {
+                name: "SqrtISWAP".to_owned().into(),
+                target_indices: vec![q0, q1].into(),
+                ..Default::default()
+            },
+            Self::ECR(q0, q1) => GenericGateProperty {
+                name: "ECR".to_owned().into(),
+                target_indices: vec![q0, q1].into(),
+                ..Default::default()
+            },
             Self::TOFFOLI(q0, q1, q2) => GenericGateProperty {
                 name: "TOFFOLI".to_owned().into(),
                 control_indices: vec![q0, q1].into(),
@@ -406,6 +435,373 @@ impl QuantumGate<f64> {
             Self::Other(o) => *o,
         }
     }
+
+    /// Prepends `control_index` to the controls. X, Z, CNOT and the rotations become
+    /// their named controlled variants; other single-target gates, including CZ and
+    /// the controlled rotations, become a generic gate named after the base gate,
+    /// e.g. `Z` with two controls. Measurements, gates on several targets and the
+    /// doubly controlled TOFFOLI cannot be controlled.
+    pub fn controlled(&self, control_index: usize) -> PyResult<Self> {
+        if self.get_qubits().contains(&control_index) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Qubit {} is already used by the gate.",
+                control_index
+            )));
+        }
+        match self {
+            Self::X(q) => Ok(Self::CNOT(control_index, *q)),
+            Self::Z(q) => Ok(Self::CZ(control_index, *q)),
+            Self::CNOT(c, t) => Ok(Self::TOFFOLI(control_index, *c, *t)),
+            Self::RX(q, p) => Ok(Self::CRX(control_index, *q, *p)),
+            Self::RY(q, p) => Ok(Self::CRY(control_index, *q, *p)),
+            Self::RZ(q, p) => Ok(Self::CRZ(control_index, *q, *p)),
+            // The phase is applied only when the control is 1.
+            Self::GlobalPhase(_, p) => Ok(Self::U1(control_index, *p)),
+            Self::Other(o) if o.classical_indices.is_empty() => {
+                let mut prop = o.as_ref().clone();
+                prop.control_indices.insert(0, control_index);
+                Ok(Self::Other(Box::new(prop)))
+            }
+            _ => {
+                let mut prop = self.clone().into_property();
+                let base = match self {
+                    Self::CZ(_, _) => Some("Z"),
+                    Self::CRX(_, _, _) => Some("RX"),
+                    Self::CRY(_, _, _) => Some("RY"),
+                    Self::CRZ(_, _, _) => Some("RZ"),
+                    Self::Measurement(_, _) | Self::UnitaryMatrix(_, _) => None,
+                    _ if prop.target_indices.len() == 1 && prop.control_indices.is_empty() => {
+                        Some(prop.name.as_str())
+                    }
+                    _ => None,
+                };
+                let Some(base) = base.map(str::to_owned) else {
+                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "{} gate cannot be controlled.",
+                        &prop.name
+                    )));
+                };
+                prop.name = base;
+                prop.control_indices.insert(0, control_index);
+                Ok(Self::Other(Box::new(prop)))
+            }
+        }
+    }
+
+    /// Drops the controls in `assume_control_set`, which are assumed to be in |1>,
+    /// e.g. TOFFOLI(0, 1, 2) becomes CNOT(1, 2) for `[0]` and X(2) for `[0, 1]`.
+    pub fn uncontrol(&self, assume_control_set: &[usize]) -> PyResult<Self> {
+        let prop = self.clone().into_property();
+        if let Some(q) = assume_control_set
+            .iter()
+            .find(|q| !prop.control_indices.contains(q))
+        {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Qubit {} is not a control of the {} gate.",
+                q, prop.name
+            )));
+        }
+        let remaining: Vec<usize> = prop
+            .control_indices
+            .iter()
+            .copied()
+            .filter(|q| !assume_control_set.contains(q))
+            .collect();
+        if remaining.len() == prop.control_indices.len() {
+            return Ok(self.clone());
+        }
+        Ok(match (self, remaining.as_slice()) {
+            (Self::CNOT(_, t), _) => Self::X(*t),
+            (Self::CZ(_, t), _) => Self::Z(*t),
+            (Self::CRX(_, t, p), _) => Self::RX(*t, *p),
+            (Self::CRY(_, t, p), _) => Self::RY(*t, *p),
+            (Self::CRZ(_, t, p), _) => Self::RZ(*t, *p),
+            (Self::TOFFOLI(_, _, t), [c]) => Self::CNOT(*c, *t),
+            (Self::TOFFOLI(_, _, t), _) => Self::X(*t),
+            _ => Self::Other(Box::new(GenericGateProperty {
+                control_indices: remaining,
+                ..prop
+            })),
+        })
+    }
+
+    pub fn inverse(&self) -> PyResult<Self> {
+        use std::f64::consts::PI;
+        match self {
+            Self::Identity(_)
+            | Self::X(_)
+            | Self::Y(_)
+            | Self::Z(_)
+            | Self::H(_)
+            | Self::CNOT(_, _)
+            | Self::CZ(_, _)
+            | Self::SWAP(_, _)
+            | Self::ECR(_, _)
+            | Self::TOFFOLI(_, _, _)
+            | Self::Pauli(_, _) => Ok(self.clone()),
+            Self::S(q) => Ok(Self::Sdag(*q)),
+            Self::Sdag(q) => Ok(Self::S(*q)),
+            Self::SqrtX(q) => Ok(Self::SqrtXdag(*q)),
+            Self::SqrtXdag(q) => Ok(Self::SqrtX(*q)),
+            Self::SqrtY(q) => Ok(Self::SqrtYdag(*q)),
+            Self::SqrtYdag(q) => Ok(Self::SqrtY(*q)),
+            Self::T(q) => Ok(Self::Tdag(*q)),
+            Self::Tdag(q) => Ok(Self::T(*q)),
+            Self::RX(q, p) => Ok(Self::RX(*q, -p)),
+            Self::RY(q, p) => Ok(Self::RY(*q, -p)),
+            Self::RZ(q, p) => Ok(Self::RZ(*q, -p)),
+            Self::U1(q, lmd) => Ok(Self::U1(*q, -lmd)),
+            Self::GlobalPhase(q, p) => Ok(Self::GlobalPhase(*q, -p)),
+            Self::CRX(c, t, p) => Ok(Self::CRX(*c, *t, -p)),
+            Self::CRY(c, t, p) => Ok(Self::CRY(*c, *t, -p)),
+            Self::CRZ(c, t, p) => Ok(Self::CRZ(*c, *t, -p)),
+            // U2(phi, lmd) = U3(pi/2, phi, lmd), and U3(theta, phi, lmd)^dag = U3(-theta, -lmd, -phi).
+            Self::U2(q, phi, lmd) => Ok(Self::U2(*q, -lmd - PI, -phi + PI)),
+            Self::U3(q, theta, phi, lmd) => Ok(Self::U3(*q, -theta, -lmd, -phi)),
+            Self::UnitaryMatrix(qs, mat) => {
+                Ok(Self::UnitaryMatrix(qs.clone(), matrix::dagger(mat)))
+            }
+            // The inverses are not named gates.
+            Self::ISWAP(q0, q1) | Self::SqrtISWAP(q0, q1) => Ok(Self::UnitaryMatrix(
+                vec![*q0, *q1],
+                matrix::dagger(&self.try_matrix().unwrap()),
+            )),
+            Self::PauliRotation(qs, ps, a) => Ok(Self::PauliRotation(qs.clone(), ps.clone(), -a)),
+            Self::Measurement(_, _) | Self::Other(_) => {
+                Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Inverse of {} gate is not defined.",
+                    &self.clone().into_property().name
+                )))
+            }
+        }
+    }
+
+    /// The `(qubit, classical bit)` pairs of a Measurement gate.
+    pub fn measurement_pairs(&self) -> PyResult<Vec<(usize, usize)>> {
+        match self {
+            Self::Measurement(qs, cs) => Ok(qs.iter().copied().zip(cs.iter().copied()).collect()),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "{} gate is not a measurement.",
+                &self.clone().into_property().name
+            ))),
+        }
+    }
+
+    /// The Pauli operator of X, Y, Z, Pauli and PauliRotation gates as letters and
+    /// qubit indices in target order, e.g. `"X0 Z2"`. Identity factors are omitted.
+    /// Other gates give an empty string, or an error when `strict` is set.
+    pub fn pauli_string(&self, strict: bool) -> PyResult<String> {
+        let factors: Vec<(usize, u8)> = match self {
+            Self::X(q) => vec![(*q, 1)],
+            Self::Y(q) => vec![(*q, 2)],
+            Self::Z(q) => vec![(*q, 3)],
+            Self::Pauli(qs, ps) | Self::PauliRotation(qs, ps, _) => {
+                if qs.len() != ps.len() {
+                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "{} gate has {} Pauli ids for {} qubits.",
+                        &self.clone().into_property().name,
+                        ps.len(),
+                        qs.len()
+                    )));
+                }
+                qs.iter().copied().zip(ps.iter().copied()).collect()
+            }
+            _ if strict => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "{} gate is not a Pauli gate.",
+                    &self.clone().into_property().name
+                )))
+            }
+            _ => vec![],
+        };
+        Ok(factors
+            .into_iter()
+            .filter(|(_, p)| *p != 0)
+            .map(|(q, p)| match ["I", "X", "Y", "Z"].get(p as usize) {
+                Some(symbol) => Ok(format!("{}{}", symbol, q)),
+                None => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Invalid Pauli id {} for qubit {}.",
+                    p, q
+                ))),
+            })
+            .collect::<PyResult<Vec<_>>>()?
+            .join(" "))
+    }
+
+    pub fn angle(&self) -> PyResult<f64> {
+        match self {
+            Self::RX(_, p) | Self::RY(_, p) | Self::RZ(_, p) | Self::PauliRotation(_, _, p) => {
+                Ok(*p)
+            }
+            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "{} gate does not have a rotation angle.",
+                &self.clone().into_property().name
+            ))),
+        }
+    }
+
+    /// The rotation angle converted to degrees. Angles are stored in radians.
+    pub fn angle_degrees(&self) -> PyResult<f64> {
+        Ok(self.angle()?.to_degrees())
+    }
+
+    /// Reduces every angle into (-pi, pi]. The rotation gates RX, RY, RZ and
+    /// PauliRotation have period 4pi, so their matrix may change by a global phase -1.
+    /// CRX, CRY and CRZ are left unchanged, since that phase would be relative.
+    pub fn normalized(&self) -> Self {
+        use std::f64::consts::{PI, TAU};
+        let wrap = |a: &f64| {
+            let r = a.rem_euclid(TAU);
+            if r > PI {
+                r - TAU
+            } else {
+                r
+            }
+        };
+        match self {
+            Self::RX(q, p) => Self::RX(*q, wrap(p)),
+            Self::RY(q, p) => Self::RY(*q, wrap(p)),
+            Self::RZ(q, p) => Self::RZ(*q, wrap(p)),
+            Self::U1(q, lmd) => Self::U1(*q, wrap(lmd)),
+            Self::GlobalPhase(q, p) => Self::GlobalPhase(*q, wrap(p)),
+            Self::U2(q, phi, lmd) => Self::U2(*q, wrap(phi), wrap(lmd)),
+            Self::U3(q, theta, phi, lmd) => Self::U3(*q, wrap(theta), wrap(phi), wrap(lmd)),
+            Self::PauliRotation(qs, ps, p) => Self::PauliRotation(qs.clone(), ps.clone(), wrap(p)),
+            _ => self.clone(),
+        }
+    }
+
+    /// The same gate with its indices in ascending order wherever the order does
+    /// not change the gate. CZ, SWAP, ISWAP and SqrtISWAP are symmetric in their
+    /// two qubits, so CZ puts the smaller one in the control. TOFFOLI sorts its
+    /// controls, and Pauli, PauliRotation and Measurement sort their (qubit, id)
+    /// or (qubit, bit) pairs by qubit. Other gates are returned unchanged.
+    pub fn canonicalize(&self) -> Self {
+        fn sorted_pairs<T: Copy + Ord>(qs: &[usize], ids: &[T]) -> (Vec<usize>, Vec<T>) {
+            let mut pairs: Vec<(usize, T)> = qs.iter().copied().zip(ids.iter().copied()).collect();
+            pairs.sort_unstable();
+            pairs.into_iter().unzip()
+        }
+        let ordered = |a: usize, b: usize| (a.min(b), a.max(b));
+        match self {
+            Self::CZ(a, b) => {
+                let (a, b) = ordered(*a, *b);
+                Self::CZ(a, b)
+            }
+            Self::SWAP(a, b) => {
+                let (a, b) = ordered(*a, *b);
+                Self::SWAP(a, b)
+            }
+            Self::ISWAP(a, b) => {
+                let (a, b) = ordered(*a, *b);
+                Self::ISWAP(a, b)
+            }
+            Self::SqrtISWAP(a, b) => {
+                let (a, b) = ordered(*a, *b);
+                Self::SqrtISWAP(a, b)
+            }
+            Self::TOFFOLI(c1, c2, t) => {
+                let (c1, c2) = ordered(*c1, *c2);
+                Self::TOFFOLI(c1, c2, *t)
+            }
+            Self::Pauli(qs, ps) => {
+                let (qs, ps) = sorted_pairs(qs, ps);
+                Self::Pauli(qs, ps)
+            }
+            Self::PauliRotation(qs, ps, p) => {
+                let (qs, ps) = sorted_pairs(qs, ps);
+                Self::PauliRotation(qs, ps, *p)
+            }
+            Self::Measurement(qs, cs) => {
+                let (qs, cs) = sorted_pairs(qs, cs);
+                Self::Measurement(qs, cs)
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Multiplies the rotation angle of the gate by `factor`.
+    pub fn scale_params(&self, factor: f64) -> PyResult<Self> {
+        match self {
+            Self::RX(q, p) => Ok(Self::RX(*q, p * factor)),
+            Self::RY(q, p) => Ok(Self::RY(*q, p * factor)),
+            Self::RZ(q, p) => Ok(Self::RZ(*q, p * factor)),
+            Self::U1(q, lmd) => Ok(Self::U1(*q, lmd * factor)),
+            Self::GlobalPhase(q, p) => Ok(Self::GlobalPhase(*q, p * factor)),
+            Self::CRX(c, t, p) => Ok(Self::CRX(*c, *t, p * factor)),
+            Self::CRY(c, t, p) => Ok(Self::CRY(*c, *t, p * factor)),
+            Self::CRZ(c, t, p) => Ok(Self::CRZ(*c, *t, p * factor)),
+            Self::PauliRotation(qs, ps, p) => {
+                Ok(Self::PauliRotation(qs.clone(), ps.clone(), p * factor))
+            }
+            Self::U2(_, _, _) | Self::U3(_, _, _, _) | Self::UnitaryMatrix(_, _) => {
+                Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Parameters of {} gate cannot be scaled.",
+                    &self.clone().into_property().name
+                )))
+            }
+            Self::Other(o) if !o.params.is_empty() || o.unitary_matrix.is_some() => {
+                Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Parameters of {} gate cannot be scaled.",
+                    &o.name
+                )))
+            }
+            _ => Ok(self.clone()),
+        }
+    }
+
+    /// The parametric counterpart of the gate with the bound value dropped.
+    pub fn as_parametric(&self) -> PyResult<ParametricQuantumGate> {
+        use crate::circuit::gates;
+        match self {
+            Self::RX(q, _) => Ok(gates::parametric_rx(*q)),
+            Self::RY(q, _) => Ok(gates::parametric_ry(*q)),
+            Self::RZ(q, _) => Ok(gates::parametric_rz(*q)),
+            Self::PauliRotation(qs, ps, _) => {
+                gates::parametric_pauli_rotation(qs.clone(), ps.clone())
+            }
+            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "{} gate does not have a parametric counterpart.",
+                &self.clone().into_property().name
+            ))),
+        }
+    }
+
+    /// Relabels the qubits by `mapping` and the classical bits by `classical_mapping`.
+    /// Indices missing from the mapping are kept unless `strict` is set.
+    pub fn remap_qubits(
+        &self,
+        mapping: &HashMap<usize, usize>,
+        classical_mapping: Option<&HashMap<usize, usize>>,
+        strict: bool,
+    ) -> PyResult<Self> {
+        let remap = |indices: &mut Vec<usize>, mapping: &HashMap<usize, usize>, kind: &str| {
+            for i in indices.iter_mut() {
+                match mapping.get(i) {
+                    Some(j) => *i = *j,
+                    None if strict => {
+                        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                            "{} index {} is missing from the mapping.",
+                            kind, i
+                        )))
+                    }
+                    None => {}
+                }
+            }
+            Ok(())
+        };
+        let mut prop = self.clone().into_property();
+        remap(&mut prop.target_indices, mapping, "Qubit")?;
+        remap(&mut prop.control_indices, mapping, "Qubit")?;
+        if let Some(classical_mapping) = classical_mapping {
+            remap(&mut prop.classical_indices, classical_mapping, "Classical")?;
+        }
+        crate::circuit::gates::check_distinct_indices(
+            &prop.name,
+            &[&prop.target_indices[..], &prop.control_indices[..]].concat(),
+        )?;
+        Ok(Self::from_property(prop)?.unwrap())
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -420,6 +816,12 @@ pub struct GenericGateProperty {
     pub unitary_matrix: Option<Vec<Vec<Complex64>>>,
 }
 
+/// Whether the order of `target_indices` changes the gate named `name`. Every
+/// other gate compares, hashes and serializes its targets as a set.
+fn ordered_targets(name: &str) -> bool {
+    matches!(name, "UnitaryMatrix" | "ECR")
+}
+
 fn unordered_eq<I0, I1>(lhs: I0, rhs: I1) -> bool
 where
     I0: IntoIterator,
@@ -443,15 +845,18 @@ fn test_unordered_eq() {
     assert!(unordered_eq(&[0, 1], &[1, 0]));
 }
 
-impl PartialEq for GenericGateProperty {
-    fn eq(&self, other: &Self) -> bool {
+impl GenericGateProperty {
+    /// Compares the gates in the same way as `PartialEq` except that `params` and
+    /// `unitary_matrix` are only required to have the same shape, i.e. whether
+    /// the two gates have the same name and wiring.
+    pub fn structurally_equal(&self, other: &Self) -> bool {
         if &self.name != &other.name
             || !unordered_eq(&self.control_indices, &other.control_indices)
-            || &self.params != &other.params
+            || self.params.len() != other.params.len()
         {
             return false;
         }
-        if &self.name == "UnitaryMatrix" {
+        if ordered_targets(&self.name) {
             if &self.target_indices != &other.target_indices {
                 return false;
             }
@@ -474,24 +879,454 @@ impl PartialEq for GenericGateProperty {
                 return false;
             }
         }
-        &self.unitary_matrix == &other.unitary_matrix
+        match (&self.unitary_matrix, &other.unitary_matrix) {
+            (Some(lhs), Some(rhs)) => {
+                lhs.len() == rhs.len() && lhs.iter().zip(rhs).all(|(l, r)| l.len() == r.len())
+            }
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Same as `PartialEq` but `params` and `unitary_matrix` entries are compared
+    /// with the tolerance `|a - b| <= atol + rtol * |b|`.
+    pub fn isclose(&self, other: &Self, rtol: f64, atol: f64) -> bool {
+        if !self.structurally_equal(other) {
+            return false;
+        }
+        let close = |a: Complex64, b: Complex64| (a - b).norm() <= atol + rtol * b.norm();
+        self.params
+            .iter()
+            .zip(&other.params)
+            .all(|(a, b)| close((*a).into(), (*b).into()))
+            && match (&self.unitary_matrix, &other.unitary_matrix) {
+                (Some(lhs), Some(rhs)) => lhs
+                    .iter()
+                    .flatten()
+                    .zip(rhs.iter().flatten())
+                    .all(|(a, b)| close(*a, *b)),
+                _ => true,
+            }
+    }
+}
+
+/// Exact equality. Qubit indices are compared as sets, except for the targets of
+/// UnitaryMatrix gates whose order defines the basis of the matrix, and Pauli ids
+/// and classical indices are compared as pairs with their target qubits. `params`
+/// are compared in order and without tolerance; see `isclose` for the latter.
+impl PartialEq for GenericGateProperty {
+    fn eq(&self, other: &Self) -> bool {
+        self.structurally_equal(other)
+            && self.params == other.params
+            && self.unitary_matrix == other.unitary_matrix
+    }
+}
+
+/// Gates with NaN parameters are not equal to themselves and should not be
+/// used as keys.
+impl Eq for GenericGateProperty {}
+
+/// Consistent with `PartialEq`: unordered index lists are sorted and floats are
+/// hashed by their bits with -0.0 folded into 0.0.
+impl std::hash::Hash for GenericGateProperty {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let float_bits = |v: f64| if v == 0.0 { 0 } else { v.to_bits() };
+        // Index lists are compared as sets, so duplicates are dropped as well.
+        let sorted = |mut v: Vec<(usize, u64)>| {
+            v.sort_unstable();
+            v.dedup();
+            v
+        };
+        self.name.hash(state);
+        if ordered_targets(&self.name) {
+            self.target_indices.hash(state);
+        } else {
+            sorted(self.target_indices.iter().map(|q| (*q, 0)).collect()).hash(state);
+        }
+        sorted(self.control_indices.iter().map(|q| (*q, 0)).collect()).hash(state);
+        sorted(
+            self.target_indices
+                .iter()
+                .zip(&self.pauli_ids)
+                .map(|(q, p)| (*q, *p as u64))
+                .collect(),
+        )
+        .hash(state);
+        if self.name == "Measurement" {
+            sorted(
+                self.target_indices
+                    .iter()
+                    .zip(&self.classical_indices)
+                    .map(|(q, c)| (*q, *c as u64))
+                    .collect(),
+            )
+            .hash(state);
+        }
+        for p in &self.params {
+            float_bits(*p).hash(state);
+        }
+        match &self.unitary_matrix {
+            Some(mat) => {
+                mat.len().hash(state);
+                for row in mat {
+                    row.len().hash(state);
+                    for c in row {
+                        float_bits(c.re).hash(state);
+                        float_bits(c.im).hash(state);
+                    }
+                }
+            }
+            None => usize::MAX.hash(state),
+        }
+    }
+}
+
+#[test]
+fn test_canonicalize() {
+    assert_eq!(QuantumGate::CZ(3, 1).canonicalize(), QuantumGate::CZ(1, 3));
+    assert_eq!(
+        QuantumGate::SWAP(2, 0).canonicalize(),
+        QuantumGate::SWAP(0, 2)
+    );
+    assert_eq!(
+        QuantumGate::TOFFOLI(4, 1, 0).canonicalize(),
+        QuantumGate::TOFFOLI(1, 4, 0)
+    );
+    assert_eq!(
+        QuantumGate::PauliRotation(vec![2, 0], vec![3, 1], 0.5).canonicalize(),
+        QuantumGate::PauliRotation(vec![0, 2], vec![1, 3], 0.5)
+    );
+    assert_eq!(
+        QuantumGate::<f64>::Measurement(vec![1, 0], vec![0, 1]).canonicalize(),
+        QuantumGate::Measurement(vec![0, 1], vec![1, 0])
+    );
+    assert_eq!(
+        QuantumGate::CNOT(1, 0).canonicalize(),
+        QuantumGate::CNOT(1, 0)
+    );
+}
+
+#[test]
+fn test_disjoint_from() {
+    let toffoli: QuantumGate<f64> = QuantumGate::TOFFOLI(0, 1, 2);
+    assert!(toffoli.disjoint_from(&QuantumGate::CNOT(3, 4)));
+    assert!(!toffoli.disjoint_from(&QuantumGate::X(1)));
+    assert!(!QuantumGate::X(1).disjoint_from(&toffoli));
+    assert!(!QuantumGate::Pauli(vec![0, 5], vec![0, 1]).disjoint_from(&QuantumGate::RZ(0, 0.5)));
+}
+
+#[test]
+fn test_ecr_targets_ordered() {
+    let prop = |a, b| QuantumGate::ECR(a, b).into_property();
+    assert_ne!(prop(0, 1), prop(1, 0));
+    assert!(!prop(0, 1).structurally_equal(&prop(1, 0)));
+    assert_ne!(prop(0, 1).canonical_repr(), prop(1, 0).canonical_repr());
+    assert_eq!(
+        QuantumGate::SWAP(0, 1).into_property(),
+        QuantumGate::SWAP(1, 0).into_property()
+    );
+}
+
+#[test]
+fn test_structurally_equal() {
+    let rx = QuantumGate::RX(0, 0.5).into_property();
+    let rx_shifted = QuantumGate::RX(0, 0.5 + 1e-12).into_property();
+    assert!(rx.structurally_equal(&rx_shifted));
+    assert!(rx != rx_shifted);
+    assert!(rx.isclose(&rx_shifted, 1e-9, 0.0));
+    assert!(!rx.structurally_equal(&QuantumGate::RX(1, 0.5).into_property()));
+    assert!(!rx.structurally_equal(&QuantumGate::RY(0, 0.5).into_property()));
+}
+
+#[test]
+fn test_hash_consistent_with_eq() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    fn hash_of<T: Hash>(v: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        v.hash(&mut hasher);
+        hasher.finish()
+    }
+    let qubits = [0, 3, 5];
+    let paulis = [1, 2, 3];
+    for (i, j) in [(0, 1), (1, 2), (0, 2)] {
+        let mut qs = qubits.to_vec();
+        let mut ps = paulis.to_vec();
+        qs.swap(i, j);
+        ps.swap(i, j);
+        let lhs = QuantumGate::PauliRotation(qubits.to_vec(), paulis.to_vec(), 0.0);
+        let rhs = QuantumGate::PauliRotation(qs, ps, -0.0);
+        assert_eq!(lhs.clone().into_property(), rhs.clone().into_property());
+        assert_eq!(hash_of(&lhs), hash_of(&rhs));
+    }
+    for (lhs, rhs) in [
+        (QuantumGate::RX(0, 0.0), QuantumGate::RX(0, -0.0)),
+        (QuantumGate::TOFFOLI(0, 1, 2), QuantumGate::TOFFOLI(1, 0, 2)),
+        (
+            QuantumGate::Measurement(vec![0, 1], vec![2, 3]),
+            QuantumGate::Measurement(vec![1, 0], vec![3, 2]),
+        ),
+    ] {
+        assert_eq!(lhs.clone().into_property(), rhs.clone().into_property());
+        assert_eq!(hash_of(&lhs), hash_of(&rhs));
+    }
+    let set: std::collections::HashSet<_> =
+        [QuantumGate::X(0), QuantumGate::X(0), QuantumGate::X(1)].into();
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_hash_negative_zero_matrix() {
+    use std::collections::HashSet;
+    let (one, zero, neg_zero) = (
+        Complex64::new(1.0, 0.0),
+        Complex64::new(0.0, 0.0),
+        Complex64::new(-0.0, -0.0),
+    );
+    let lhs = QuantumGate::UnitaryMatrix(vec![0], vec![vec![one, zero], vec![zero, one]]);
+    let rhs = QuantumGate::UnitaryMatrix(vec![0], vec![vec![one, neg_zero], vec![neg_zero, one]]);
+    assert_eq!(lhs, rhs);
+    let set: HashSet<_> = [lhs.into_property(), rhs.into_property()].into();
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn test_parametric_eq_ignores_unused_fields() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    fn hash_of<T: Hash>(v: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        v.hash(&mut hasher);
+        hasher.finish()
     }
+    let prop = GenericGateProperty {
+        name: "ParametricPauliRotation".to_owned(),
+        target_indices: vec![0, 2],
+        pauli_ids: vec![1, 3],
+        ..Default::default()
+    };
+    let gate = ParametricQuantumGate(prop.clone(), None);
+    let other = ParametricQuantumGate(
+        GenericGateProperty {
+            classical_indices: vec![1],
+            params: vec![0.5],
+            unitary_matrix: Some(vec![]),
+            ..prop.clone()
+        },
+        None,
+    );
+    assert_eq!(gate, other);
+    assert_eq!(hash_of(&gate), hash_of(&other));
+    let reordered = ParametricQuantumGate(
+        GenericGateProperty {
+            target_indices: vec![2, 0],
+            pauli_ids: vec![3, 1],
+            ..prop.clone()
+        },
+        None,
+    );
+    assert_eq!(gate, reordered);
+    assert_eq!(hash_of(&gate), hash_of(&reordered));
+    let labelled = ParametricQuantumGate(prop.clone(), Some(vec!["theta".to_owned()]));
+    assert_ne!(gate, labelled);
+    assert_ne!(
+        labelled,
+        ParametricQuantumGate(prop.clone(), Some(vec!["phi".to_owned()]))
+    );
+    assert_eq!(
+        labelled,
+        ParametricQuantumGate(prop, Some(vec!["theta".to_owned()]))
+    );
+}
+
+#[test]
+fn test_compat_string_floats() {
+    let rx = QuantumGate::RX(0, 1.0).into_property();
+    assert!(rx.get_compat_string().contains("params=(1.0,)"));
+    let rx = QuantumGate::RX(0, 0.1 + 0.2).into_property();
+    assert!(rx
+        .get_compat_string()
+        .contains("params=(0.30000000000000004,)"));
+    assert_eq!(py_float(-0.0), "-0.0");
+    assert_eq!(py_float(1e-300), "1e-300");
+    assert_eq!(py_float(f64::NEG_INFINITY), "-float('inf')");
+    // The sign of zero is kept, e.g. for -i = (-0.0, -1.0).
+    let unitary = QuantumGate::UnitaryMatrix(vec![0], matrix::pauli(2)).into_property();
+    assert!(unitary.get_compat_string().ends_with(
+        "unitary_matrix=((complex(0.0, 0.0), complex(-0.0, -1.0)), \
+         (complex(0.0, 1.0), complex(0.0, 0.0))))"
+    ));
+}
+
+#[test]
+fn test_locality() {
+    let pauli = QuantumGate::<f64>::Pauli(vec![0, 1, 2], vec![1, 0, 3]);
+    assert_eq!(pauli.locality(), 2);
+    assert_eq!(pauli.sorted_qubits().len(), 3);
+    assert_eq!(QuantumGate::<f64>::Pauli(vec![4], vec![0]).locality(), 0);
+    assert_eq!(QuantumGate::<f64>::TOFFOLI(0, 1, 2).locality(), 3);
+    assert_eq!(QuantumGate::RZ(5, 0.5).locality(), 1);
+}
+
+#[test]
+fn test_sort_key() {
+    let mut props: Vec<GenericGateProperty> = vec![
+        QuantumGate::CNOT(2, 1).into_property(),
+        QuantumGate::X(3).into_property(),
+        QuantumGate::CNOT(0, 1).into_property(),
+        QuantumGate::X(0).into_property(),
+        QuantumGate::SWAP(4, 0).into_property(),
+    ];
+    props.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+    let keys: Vec<_> = props.iter().map(|p| p.sort_key()).collect();
+    assert_eq!(
+        keys,
+        vec![
+            ("CNOT", 1, &[1][..], &[0][..]),
+            ("CNOT", 1, &[1][..], &[2][..]),
+            ("SWAP", 0, &[4, 0][..], &[][..]),
+            ("X", 0, &[0][..], &[][..]),
+            ("X", 3, &[3][..], &[][..]),
+        ]
+    );
+    let empty = GenericGateProperty {
+        name: "Custom".to_owned(),
+        ..Default::default()
+    };
+    assert_eq!(empty.sort_key().1, -1);
+}
+
+#[test]
+fn test_display() {
+    let rx = QuantumGate::RX(0, std::f64::consts::FRAC_PI_4).into_property();
+    assert_eq!(rx.display(), "RX(θ=0.79) q0");
+    assert_eq!(rx.pretty(), "RX(0.785) on q0");
+    let u3 = QuantumGate::U3(2, 1.0, -0.001, 0.5).into_property();
+    assert_eq!(u3.display(), "U3(θ=1, φ=0, λ=0.5) q2");
+    let toffoli = QuantumGate::TOFFOLI(0, 1, 2).into_property();
+    assert_eq!(toffoli.display(), "TOFFOLI q0 q1 q2");
+    let rotation = QuantumGate::PauliRotation(vec![0, 2], vec![1, 3], 0.5).into_property();
+    assert_eq!(rotation.display(), "PauliRotation(XZ, θ=0.5) q0 q2");
+    let measurement = QuantumGate::Measurement(vec![0, 1], vec![1, 0]).into_property();
+    assert_eq!(measurement.display(), "Measurement q0->c1,q1->c0");
 }
 
 #[pyclass(subclass, frozen, eq, module = "quri_parts.rust.circuit.gate")]
-#[derive(Clone, Debug, PartialEq)]
-pub struct ParametricQuantumGate(pub(crate) GenericGateProperty);
+/// The second field holds optional labels, one per parameter, that let tools map
+/// parameters shared between gates. They do not affect the unitary.
+#[derive(Clone, Debug)]
+pub struct ParametricQuantumGate(
+    pub(crate) GenericGateProperty,
+    pub(crate) Option<Vec<String>>,
+);
+
+/// Only `name`, `target_indices`, `control_indices` and `pauli_ids` are compared.
+/// Parametric gates do not use `classical_indices`, `params` or `unitary_matrix`.
+/// Labels are compared when present, so a labelled gate never equals an unlabelled
+/// one, while unlabelled gates compare and hash as if labels did not exist.
+impl PartialEq for ParametricQuantumGate {
+    fn eq(&self, other: &Self) -> bool {
+        self.meaningful_property() == other.meaningful_property() && self.1 == other.1
+    }
+}
+
+impl Eq for ParametricQuantumGate {}
+
+impl std::hash::Hash for ParametricQuantumGate {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.meaningful_property().hash(state);
+        if let Some(labels) = &self.1 {
+            labels.hash(state);
+        }
+    }
+}
+
+impl ParametricQuantumGate {
+    /// The same gate with one label per parameter. Gates not known to the crate
+    /// accept any number of labels.
+    pub fn with_param_labels(&self, labels: Vec<String>) -> PyResult<Self> {
+        if let Some(count) = self.parameter_count() {
+            if labels.len() != count {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "{} gate takes {} parameter(s) but {} labels were given.",
+                    &self.0.name,
+                    count,
+                    labels.len()
+                )));
+            }
+        }
+        Ok(ParametricQuantumGate(self.0.clone(), Some(labels)))
+    }
+
+    fn meaningful_property(&self) -> GenericGateProperty {
+        GenericGateProperty {
+            classical_indices: vec![],
+            params: vec![],
+            unitary_matrix: None,
+            ..self.0.clone()
+        }
+    }
+
+    /// Number of free parameters, or `None` for gates not known to the crate.
+    pub fn parameter_count(&self) -> Option<usize> {
+        match self.0.name.as_str() {
+            "ParametricRX" | "ParametricRY" | "ParametricRZ" | "ParametricPauliRotation" => Some(1),
+            _ => None,
+        }
+    }
+
+    /// Coefficients and parameter shifts `(c, s)` such that the derivative of an
+    /// expectation value is `sum(c * f(theta + s))`. All known parametric gates
+    /// are `exp(-i theta P / 2)` for a Pauli `P`, so they share the two-term rule.
+    pub fn shift_rule(&self) -> PyResult<Vec<(f64, f64)>> {
+        use std::f64::consts::FRAC_PI_2;
+        match self.parameter_count() {
+            Some(_) => Ok(vec![(0.5, FRAC_PI_2), (-0.5, -FRAC_PI_2)]),
+            None => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "{} gate has no known parameter-shift rule.",
+                &self.0.name
+            ))),
+        }
+    }
+
+    /// Returns the concrete gate with `values` filled in as its parameters.
+    pub fn bind(&self, values: &[f64]) -> PyResult<QuantumGate> {
+        let prop = &self.0;
+        let expected = self.parameter_count().ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!("{} gate cannot be bound.", &prop.name))
+        })?;
+        if values.len() != expected {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "{} gate takes {} parameter(s) but {} were given.",
+                &prop.name,
+                expected,
+                values.len()
+            )));
+        }
+        match prop.name.as_str() {
+            "ParametricRX" => Ok(QuantumGate::RX(prop.target_indices[0], values[0])),
+            "ParametricRY" => Ok(QuantumGate::RY(prop.target_indices[0], values[0])),
+            "ParametricRZ" => Ok(QuantumGate::RZ(prop.target_indices[0], values[0])),
+            _ => Ok(QuantumGate::PauliRotation(
+                prop.target_indices.clone(),
+                prop.pauli_ids.clone(),
+                values[0],
+            )),
+        }
+    }
+}
 
 #[pymethods]
 impl ParametricQuantumGate {
     #[new]
     #[pyo3(
-        signature = (name, target_indices, control_indices=Vec::new(), pauli_ids=Vec::new()),
+        signature = (name, target_indices, control_indices=Vec::new(), pauli_ids=Vec::new(), param_labels=None),
         text_signature = "(
             name: str,
             target_indices: Sequence[int],
             control_indices: Sequence[int] = [],
             pauli_ids: Sequence[int] = [],
+            param_labels: Optional[Sequence[str]] = None,
         )"
     )]
     fn py_new<'py>(
@@ -499,7 +1334,14 @@ impl ParametricQuantumGate {
         target_indices: Vec<usize>,
         control_indices: Vec<usize>,
         pauli_ids: Vec<u8>,
-    ) -> ParametricQuantumGate {
+        param_labels: Option<Vec<String>>,
+    ) -> PyResult<ParametricQuantumGate> {
+        match name.as_str() {
+            "ParametricPauliRotation" => {
+                crate::circuit::gates::check_pauli_ids(&name, &target_indices, &pauli_ids, 1)?
+            }
+            _ => crate::circuit::gates::check_pauli_id_range(&name, &pauli_ids, 0)?,
+        }
         let prop = GenericGateProperty {
             name: name.clone().into(),
             target_indices: target_indices.into(),
@@ -509,47 +1351,108 @@ impl ParametricQuantumGate {
             params: vec![].into(),
             unitary_matrix: None,
         };
-        ParametricQuantumGate(prop)
+        let gate = ParametricQuantumGate(prop, None);
+        match param_labels {
+            None => Ok(gate),
+            Some(labels) => gate.with_param_labels(labels),
+        }
     }
 
     #[pyo3(name = "__repr__")]
     fn py_repr(&self) -> String {
-        self.0.get_compat_string_parametric()
+        let mut repr = self.0.get_compat_string_parametric();
+        if let Some(labels) = &self.1 {
+            let labels: Vec<String> = labels.iter().map(|l| format!("{:?}", l)).collect();
+            repr.insert_str(
+                repr.len() - 1,
+                &format!(", param_labels=({})", format_tuple(&labels)),
+            );
+        }
+        repr
     }
 
+    /// Reconstructs through `ParametricQuantumGate.__new__(cls, *args)` with the
+    /// actual class, so Python subclasses unpickle as themselves even if their
+    /// `__new__` or `__init__` take other arguments. Their `__dict__` is restored
+    /// as the state.
     #[pyo3(name = "__reduce__")]
-    fn py_reduce(
-        slf: &Bound<'_, Self>,
-    ) -> PyResult<(PyObject, (String, Vec<usize>, Vec<usize>, Vec<u8>))> {
+    fn py_reduce<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyAny>, PyObject, Option<Bound<'py, PyAny>>)> {
+        let py = slf.py();
         let data = &slf.get().0;
+        let args = (
+            slf.get_type(),
+            data.name.clone(),
+            data.target_indices.clone(),
+            data.control_indices.clone(),
+            data.pauli_ids.clone(),
+            slf.get().1.clone(),
+        );
         Ok((
-            Bound::new(slf.py(), ParametricQuantumGate(slf.get().0.clone()))
-                .unwrap()
-                .getattr("__class__")
-                .unwrap()
-                .unbind(),
-            (
-                data.name.clone().into(),
-                data.target_indices.clone().into(),
-                data.control_indices.clone().into(),
-                data.pauli_ids.clone().into(),
-            ),
+            py.get_type_bound::<Self>().getattr("__new__")?,
+            args.into_py(py),
+            slf.getattr("__dict__").ok(),
         ))
     }
 
+    #[pyo3(name = "bind")]
+    fn py_bind(&self, values: Vec<f64>) -> PyResult<QuantumGate> {
+        self.bind(&values)
+    }
+
+    /// Binds slot `i` to `param_map[keys[i]]`, so gates that share a key share the
+    /// value.
+    #[pyo3(text_signature = "(param_map: Mapping[Hashable, float], keys: Sequence[Hashable])")]
+    fn substitute(
+        &self,
+        param_map: &Bound<'_, PyAny>,
+        keys: Vec<Bound<'_, PyAny>>,
+    ) -> PyResult<QuantumGate> {
+        let values = keys
+            .iter()
+            .map(|key| match param_map.get_item(key) {
+                Ok(v) => v.extract::<f64>(),
+                Err(e) if e.is_instance_of::<pyo3::exceptions::PyKeyError>(key.py()) => {
+                    Err(pyo3::exceptions::PyKeyError::new_err(format!(
+                        "Parameter {} of {} gate is missing from param_map.",
+                        key.repr()?,
+                        &self.0.name
+                    )))
+                }
+                Err(e) => Err(e),
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        self.bind(&values)
+    }
+
+    #[pyo3(name = "shift_rule")]
+    fn py_shift_rule(&self) -> PyResult<Vec<(f64, f64)>> {
+        self.shift_rule()
+    }
+
+    #[pyo3(name = "to_qasm3")]
+    #[pyo3(text_signature = "(param_name: str)")]
+    fn py_to_qasm3(&self, param_name: &str) -> PyResult<String> {
+        self.to_qasm3(param_name)
+    }
+
+    #[pyo3(name = "__copy__")]
+    fn py_copy(&self) -> Self {
+        self.clone()
+    }
+
+    #[pyo3(name = "__deepcopy__")]
+    fn py_deepcopy(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
     #[pyo3(name = "__hash__")]
     pub(crate) fn py_hash(&self) -> u64 {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
         let mut hasher = DefaultHasher::new();
-        let data = self.0.clone();
-        data.name.hash(&mut hasher);
-        data.target_indices.hash(&mut hasher);
-        data.control_indices.hash(&mut hasher);
-        for p in data.params.iter() {
-            p.to_le_bytes().hash(&mut hasher);
-        }
-        data.pauli_ids.hash(&mut hasher);
+        self.hash(&mut hasher);
         hasher.finish()
     }
 
@@ -558,6 +1461,22 @@ impl ParametricQuantumGate {
         self.0.name.clone().into()
     }
 
+    #[getter]
+    fn get_kind(&self) -> &'static str {
+        "Parametric"
+    }
+
+    /// 0 for gates whose parameters are not known to the crate.
+    #[getter]
+    fn get_parameter_count(&self) -> usize {
+        self.parameter_count().unwrap_or(0)
+    }
+
+    #[getter]
+    fn get_param_labels(&self) -> Option<Vec<String>> {
+        self.1.clone()
+    }
+
     #[getter]
     fn get_target_indices<'py>(slf: &Bound<'py, Self>) -> Bound<'py, PyTuple> {
         let v: Vec<usize> = slf.get().0.target_indices.clone().into();
@@ -583,6 +1502,67 @@ impl ParametricQuantumGate {
     }
 }
 
+fn fnv1a_128(data: &[u8]) -> u128 {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+    data.iter()
+        .fold(OFFSET_BASIS, |h, b| (h ^ *b as u128).wrapping_mul(PRIME))
+}
+
+#[test]
+fn test_fnv1a_128() {
+    assert_eq!(fnv1a_128(b""), 0x6c62272e07bb014262b821756295c58d);
+    assert_eq!(fnv1a_128(b"a"), 0xd228cb696f1a8caf78912b704e4a8964);
+}
+
+fn qubit_list(indices: &[usize], separator: &str) -> String {
+    indices
+        .iter()
+        .map(|q| format!("q{}", q))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn pauli_symbols(pauli_ids: &[u8]) -> String {
+    pauli_ids
+        .iter()
+        .map(|p| {
+            ['I', 'X', 'Y', 'Z']
+                .get(*p as usize)
+                .copied()
+                .unwrap_or('?')
+        })
+        .collect()
+}
+
+/// `p` rounded to `decimals` places without trailing zeros, and `-0` as `0`.
+fn rounded(p: f64, decimals: usize) -> String {
+    let s = format!("{:.*}", decimals, p);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" { "0" } else { s }.to_owned()
+}
+
+/// Python expression for `p`. `{:?}` always includes a decimal point or an
+/// exponent and round-trips exactly on every platform.
+fn py_float(p: f64) -> String {
+    if p.is_nan() {
+        "float('nan')".to_owned()
+    } else if p.is_infinite() {
+        if p > 0.0 {
+            "float('inf')"
+        } else {
+            "-float('inf')"
+        }
+        .to_owned()
+    } else {
+        format!("{:?}", p)
+    }
+}
+
+fn py_complex(c: &Complex64) -> String {
+    format!("complex({}, {})", py_float(c.re), py_float(c.im))
+}
+
 fn format_tuple<T: core::fmt::Display>(input: &[T]) -> String {
     let out = input
         .iter()
@@ -597,18 +1577,168 @@ fn format_tuple<T: core::fmt::Display>(input: &[T]) -> String {
 }
 
 impl GenericGateProperty {
+    /// Deterministic representation that is equal for gates equal under `PartialEq`.
+    /// Unlike `__hash__`, it does not depend on the process or the Rust version, so
+    /// it can be persisted. Floats use the shortest representation that round-trips,
+    /// with -0.0 written as 0.0.
+    pub fn canonical_repr(&self) -> String {
+        fn real(v: f64) -> String {
+            format!("{:?}", if v == 0.0 { 0.0 } else { v })
+        }
+        fn join<T>(items: impl IntoIterator<Item = T>, f: impl Fn(T) -> String) -> String {
+            items.into_iter().map(f).collect::<Vec<_>>().join(",")
+        }
+        let mut targets: Vec<(usize, Option<u8>, Option<usize>)> = self
+            .target_indices
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                let classical = if self.name == "Measurement" {
+                    self.classical_indices.get(i).copied()
+                } else {
+                    None
+                };
+                (*t, self.pauli_ids.get(i).copied(), classical)
+            })
+            .collect();
+        if !ordered_targets(&self.name) {
+            targets.sort_unstable();
+        }
+        let mut controls = self.control_indices.clone();
+        controls.sort_unstable();
+        let matrix = match &self.unitary_matrix {
+            Some(mat) => {
+                let rows = join(mat, |row| {
+                    format!(
+                        "[{}]",
+                        join(row, |c| format!("{}:{}", real(c.re), real(c.im)))
+                    )
+                });
+                format!("[{}]", rows)
+            }
+            None => "None".to_owned(),
+        };
+        format!(
+            "{}(targets=[{}];controls=[{}];classical=[{}];params=[{}];pauli_ids=[{}];matrix={})",
+            &self.name,
+            join(&targets, |t| t.0.to_string()),
+            join(&controls, |c| c.to_string()),
+            join(targets.iter().filter_map(|t| t.2), |c| c.to_string()),
+            join(&self.params, |p| real(*p)),
+            join(targets.iter().filter_map(|t| t.1), |p| p.to_string()),
+            matrix,
+        )
+    }
+
+    /// 128-bit FNV-1a hash of `canonical_repr()`. It is stable across processes and
+    /// platforms, so it can key persisted deduplication tables.
+    pub fn content_hash(&self) -> u128 {
+        fnv1a_128(self.canonical_repr().as_bytes())
+    }
+
+    /// Concise one-line notation for logs, e.g. `RX(0.785) on q0`, `CNOT q0->q1` or
+    /// `Measurement q0->c1`. Parameters are rounded to three decimals.
+    pub fn pretty(&self) -> String {
+        let mut args: Vec<String> = vec![];
+        if !self.pauli_ids.is_empty() {
+            args.push(pauli_symbols(&self.pauli_ids));
+        }
+        args.extend(self.params.iter().map(|p| rounded(*p, 3)));
+        let mut out = self.name.clone();
+        if !args.is_empty() {
+            out.push_str(&format!("({})", args.join(", ")));
+        }
+        if self.name == "Measurement" {
+            out.push_str(&format!(" {}", self.measurement_pairs()));
+        } else if self.control_indices.is_empty() {
+            out.push_str(&format!(" on {}", qubit_list(&self.target_indices, ",")));
+        } else {
+            out.push_str(&format!(
+                " {}->{}",
+                qubit_list(&self.control_indices, ","),
+                qubit_list(&self.target_indices, ",")
+            ));
+        }
+        out
+    }
+
+    /// Short notation for end users, e.g. `RX(θ=0.79) q0` or `CNOT q0 q1` with the
+    /// qubits in `get_qubits()` order. Parameters are rounded to two decimals and
+    /// named for the standard rotations.
+    pub fn display(&self) -> String {
+        let names: &[&str] = match self.name.as_str() {
+            "RX" | "RY" | "RZ" | "CRX" | "CRY" | "CRZ" | "PauliRotation" => &["θ"],
+            "U1" => &["λ"],
+            "U2" => &["φ", "λ"],
+            "U3" => &["θ", "φ", "λ"],
+            "GlobalPhase" => &["φ"],
+            _ => &[],
+        };
+        let mut args: Vec<String> = vec![];
+        if !self.pauli_ids.is_empty() {
+            args.push(pauli_symbols(&self.pauli_ids));
+        }
+        for (i, p) in self.params.iter().enumerate() {
+            args.push(match names.get(i) {
+                Some(name) => format!("{}={}", name, rounded(*p, 2)),
+                None => rounded(*p, 2),
+            });
+        }
+        let mut out = self.name.clone();
+        if !args.is_empty() {
+            out.push_str(&format!("({})", args.join(", ")));
+        }
+        if self.name == "Measurement" {
+            out.push_str(&format!(" {}", self.measurement_pairs()));
+        } else {
+            let qubits: Vec<usize> = self
+                .control_indices
+                .iter()
+                .chain(&self.target_indices)
+                .copied()
+                .collect();
+            out.push_str(&format!(" {}", qubit_list(&qubits, " ")));
+        }
+        out
+    }
+
+    /// `(name, smallest target, targets, controls)`, comparing gates first by name
+    /// and then by where they act. The smallest target is -1 for gates without
+    /// targets. Gates that differ only in other fields get equal keys.
+    pub fn sort_key(&self) -> (&str, i64, &[usize], &[usize]) {
+        let min_target = self.target_indices.iter().min().map_or(-1, |q| *q as i64);
+        (
+            &self.name,
+            min_target,
+            &self.target_indices,
+            &self.control_indices,
+        )
+    }
+
+    fn measurement_pairs(&self) -> String {
+        self.target_indices
+            .iter()
+            .zip(&self.classical_indices)
+            .map(|(q, c)| format!("q{}->c{}", q, c))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// The `QuantumGate(...)` constructor call that rebuilds the gate. Floats use
+    /// the shortest representation that round-trips and complex entries are
+    /// written as `complex(re, im)`, so the string evaluates to an equal gate.
     pub fn get_compat_string(&self) -> String {
-        format!("QuantumGate(name='{}', target_indices=({}), control_indices=({}), classical_indices=({}), params=({}), pauli_ids=({}), unitary_matrix={})",
+        format!("QuantumGate(name='{}', target_indices=({}), control_indices=({}), classical_indices=({}), params=({}), pauli_ids=({}), unitary_matrix=({}))",
             &self.name,
             format_tuple(self.target_indices.as_slice()),
             format_tuple(self.control_indices.as_slice()),
             format_tuple(self.classical_indices.as_slice()),
-            format_tuple(self.params.as_slice()),
+            format_tuple(&self.params.iter().map(|p| py_float(*p)).collect::<Vec<_>>()),
             format_tuple(self.pauli_ids.as_slice()),
             {
                 if let Some(matrix) = &self.unitary_matrix {
-                    format_tuple(matrix.iter().map(|row| format!("({})", format_tuple(row))).collect::<Vec<_>>().as_slice())
-                }else {"()".to_owned()}
+                    format_tuple(matrix.iter().map(|row| format!("({})", format_tuple(&row.iter().map(py_complex).collect::<Vec<_>>()))).collect::<Vec<_>>().as_slice())
+                }else {"".to_owned()}
             }
         )
     }
@@ -624,22 +1754,53 @@ impl GenericGateProperty {
 
 mod wrapper {
     use super::*;
+    use std::sync::OnceLock;
     #[pyclass(frozen, module = "quri_parts.rust.circuit.gate", name = "QuantumGate")]
-    #[derive(Clone, Debug, PartialEq)]
-    struct QuantumGateWrapper(QuantumGate<f64>);
+    /// The property is built lazily from the gate and kept for the lifetime of
+    /// the (immutable) object, so that getters do not rebuild it every time.
+    ///
+    /// The last field is the optional duration in nanoseconds. It is scheduling
+    /// metadata rather than part of the gate, so equality, hashing and
+    /// serialization ignore it, and gates derived from this one do not carry it.
+    #[derive(Clone, Debug)]
+    struct QuantumGateWrapper(QuantumGate<f64>, OnceLock<GenericGateProperty>, Option<f64>);
+
+    impl QuantumGateWrapper {
+        fn new(gate: QuantumGate<f64>) -> Self {
+            Self(gate, OnceLock::new(), None)
+        }
+
+        fn property(&self) -> &GenericGateProperty {
+            self.1.get_or_init(|| self.0.clone().into_property())
+        }
+    }
+
+    impl PartialEq for QuantumGateWrapper {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for QuantumGateWrapper {}
+
+    impl std::hash::Hash for QuantumGateWrapper {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.0.hash(state)
+        }
+    }
 
     impl<T> IntoPy<T> for QuantumGate<f64>
     where
         QuantumGateWrapper: IntoPy<T>,
     {
         fn into_py(self, py: Python<'_>) -> T {
-            QuantumGateWrapper(self).into_py(py)
+            QuantumGateWrapper::new(self).into_py(py)
         }
     }
 
     impl<'py> pyo3::conversion::FromPyObject<'py> for QuantumGate<f64> {
         fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
-            Ok(<QuantumGateWrapper as pyo3::conversion::FromPyObject<'py>>::extract_bound(ob)?.0)
+            Ok(ob.downcast::<QuantumGateWrapper>()?.get().0.clone())
         }
     }
 
@@ -672,6 +1833,63 @@ mod wrapper {
                     unitary_matrix = None;
                 }
             }
+            if let Some(matrix) = &unitary_matrix {
+                if matrix.iter().any(|row| row.len() != matrix[0].len()) {
+                    return Err(InvalidUnitaryError::new_err(
+                        "All rows of the unitary matrix must have the same length.",
+                    ));
+                }
+                if matrix[0].len() != matrix.len() {
+                    return Err(InvalidUnitaryError::new_err(
+                        "The unitary matrix must be square.",
+                    ));
+                }
+                if matrix.len() != 2usize.pow(target_indices.len() as u32) {
+                    return Err(InvalidUnitaryError::new_err(
+                        "The number of qubits does not match the size of the unitary matrix.",
+                    ));
+                }
+            }
+            crate::circuit::gates::check_distinct_indices(&name, &target_indices)?;
+            crate::circuit::gates::check_distinct_indices(&name, &control_indices)?;
+            if let Some(q) = control_indices.iter().find(|q| target_indices.contains(q)) {
+                return Err(IndexConflictError::new_err(format!(
+                    "{} gate cannot use qubit {} as both a target and a control.",
+                    name, q
+                )));
+            }
+            if name == "Measurement" && target_indices.len() != classical_indices.len() {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "Number of qubits and classical bits must be same for measurement.",
+                ));
+            }
+            // Gates with other names are user-defined and may take any fields.
+            if let Some(signature) = schema::GATE_SIGNATURES.iter().find(|s| s.name == name) {
+                if name != "Measurement" && !classical_indices.is_empty() {
+                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "{} gate cannot have classical indices; only measurements write classical bits.",
+                        name
+                    )));
+                }
+                if params.len() != signature.params {
+                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "{} gate takes {} parameter{}, got {}.",
+                        name,
+                        signature.params,
+                        if signature.params == 1 { "" } else { "s" },
+                        params.len()
+                    )));
+                }
+            }
+            match name.as_str() {
+                "Pauli" => {
+                    crate::circuit::gates::check_pauli_ids(&name, &target_indices, &pauli_ids, 0)?
+                }
+                "PauliRotation" => {
+                    crate::circuit::gates::check_pauli_ids(&name, &target_indices, &pauli_ids, 1)?
+                }
+                _ => crate::circuit::gates::check_pauli_id_range(&name, &pauli_ids, 0)?,
+            }
             let prop = GenericGateProperty {
                 name: name.clone().into(),
                 target_indices: target_indices.into(),
@@ -683,17 +1901,25 @@ mod wrapper {
                     .map(|v| v.into_iter().map(Into::into).collect())
                     .into(),
             };
-            Ok(Self(QuantumGate::from_property(prop)?.ok_or(
-                pyo3::exceptions::PyValueError::new_err(format!(
-                    "Cannot initialize QuantumGate with {}",
-                    &name
-                )),
+            Ok(Self::new(QuantumGate::from_property(prop)?.ok_or(
+                UnknownGateError::new_err(format!("Cannot initialize QuantumGate with {}", &name)),
             )?))
         }
 
+        #[classattr]
+        #[allow(non_snake_case)]
+        fn __match_args__() -> (&'static str, &'static str, &'static str, &'static str) {
+            ("name", "target_indices", "control_indices", "params")
+        }
+
         #[pyo3(name = "__repr__")]
         fn py_repr(&self) -> String {
-            self.0.clone().into_property().get_compat_string()
+            self.property().get_compat_string()
+        }
+
+        #[pyo3(name = "__str__")]
+        fn py_str(&self) -> String {
+            self.property().display()
         }
 
         #[pyo3(name = "__reduce__")]
@@ -711,7 +1937,7 @@ mod wrapper {
                 Option<Vec<Vec<Complex64>>>,
             ),
         )> {
-            let data = &slf.get().0.clone().into_property();
+            let data = slf.get().property();
             Ok((
                 slf.getattr("__class__").unwrap().unbind(),
                 (
@@ -729,79 +1955,549 @@ mod wrapper {
             ))
         }
 
+        #[pyo3(name = "__copy__")]
+        fn py_copy(&self) -> Self {
+            self.clone()
+        }
+
+        #[pyo3(name = "__deepcopy__")]
+        fn py_deepcopy(&self, _memo: &Bound<'_, PyAny>) -> Self {
+            self.clone()
+        }
+
+        /// The hash is process-local; use `content_hash` or `canonical_repr` for a
+        /// portable key.
         #[pyo3(name = "__hash__")]
         pub(crate) fn py_hash(&self) -> u64 {
             use std::collections::hash_map::DefaultHasher;
             use std::hash::{Hash, Hasher};
             let mut hasher = DefaultHasher::new();
-            let data = self.0.clone().into_property();
-            data.name.hash(&mut hasher);
-            data.target_indices.hash(&mut hasher);
-            data.control_indices.hash(&mut hasher);
-            data.classical_indices.hash(&mut hasher);
-            for p in data.params.iter() {
-                p.to_le_bytes().hash(&mut hasher);
-            }
-            data.pauli_ids.hash(&mut hasher);
-            if let Some(matrix) = &data.unitary_matrix {
-                for p in matrix.iter() {
-                    for q in p.iter() {
-                        q.re.to_le_bytes().hash(&mut hasher);
-                        q.im.to_le_bytes().hash(&mut hasher);
-                    }
-                }
-            }
+            self.property().hash(&mut hasher);
             hasher.finish()
         }
 
         #[pyo3(name = "__eq__")]
         fn py_eq(&self, other: &Self) -> bool {
-            self.0.clone().into_property() == other.0.clone().into_property()
+            self.property() == other.property()
+        }
+
+        #[pyo3(signature = (other, rtol=1e-9, atol=1e-12))]
+        #[pyo3(text_signature = "(other: QuantumGate, rtol: float = 1e-9, atol: float = 1e-12)")]
+        fn isclose(&self, other: &Self, rtol: f64, atol: f64) -> bool {
+            self.property().isclose(other.property(), rtol, atol)
+        }
+
+        fn structurally_equal(&self, other: &Self) -> bool {
+            self.property().structurally_equal(other.property())
+        }
+
+        #[pyo3(signature = (atol=1e-10))]
+        #[pyo3(text_signature = "(atol: float = 1e-10)")]
+        fn is_diagonal(&self, atol: f64) -> bool {
+            self.0.is_diagonal(atol)
+        }
+
+        #[pyo3(signature = (atol=1e-10, up_to_global_phase=true))]
+        #[pyo3(text_signature = "(atol: float = 1e-10, up_to_global_phase: bool = True)")]
+        fn is_identity(&self, atol: f64, up_to_global_phase: bool) -> bool {
+            self.0.is_identity(atol, up_to_global_phase)
+        }
+
+        fn trace(&self) -> PyResult<Complex64> {
+            self.0.trace()
+        }
+
+        fn determinant(&self) -> PyResult<Complex64> {
+            self.0.determinant()
+        }
+
+        /// Number of matrix entries whose modulus exceeds `atol`.
+        #[pyo3(signature = (atol=1e-12))]
+        #[pyo3(text_signature = "(atol: float = 1e-12)")]
+        fn nnz(&self, atol: f64) -> PyResult<usize> {
+            self.0.nnz(atol)
+        }
+
+        /// The entries of the matrix whose modulus exceeds `atol` as parallel lists
+        /// `(rows, cols, values)` in row-major order, e.g. for
+        /// `scipy.sparse.coo_matrix((values, (rows, cols)))`. Indices follow the
+        /// same qubit order as `matrix()`, with qubit `i` as bit `i` of the index.
+        #[pyo3(signature = (atol=1e-12))]
+        #[pyo3(text_signature = "(atol: float = 1e-12)")]
+        fn to_coo(&self, atol: f64) -> PyResult<(Vec<usize>, Vec<usize>, Vec<Complex64>)> {
+            self.0.to_coo(atol)
+        }
+
+        /// The output basis index of each input basis index, in the `matrix()`
+        /// qubit order, for gates whose matrix is a 0/1 permutation matrix.
+        fn basis_permutation(&self) -> PyResult<Vec<usize>> {
+            self.0.basis_permutation()
+        }
+
+        /// Whether the matrix has exactly one unit-modulus entry per row and column.
+        #[pyo3(signature = (atol=1e-10))]
+        #[pyo3(text_signature = "(atol: float = 1e-10)")]
+        fn is_permutation(&self, atol: f64) -> PyResult<bool> {
+            self.0.is_permutation(atol)
+        }
+
+        fn process_fidelity(&self, other: &Self) -> PyResult<f64> {
+            self.0.process_fidelity(&other.0)
+        }
+
+        /// Targets and controls of the gate in ascending order.
+        fn qubits<'py>(slf: &Bound<'py, Self>) -> Bound<'py, PyTuple> {
+            PyTuple::new_bound(slf.py(), slf.get().0.sorted_qubits())
+        }
+
+        fn locality(&self) -> usize {
+            self.0.locality()
+        }
+
+        /// Whether the gates act on disjoint sets of targets and controls.
+        #[pyo3(text_signature = "(other: QuantumGate)")]
+        fn disjoint_from(&self, other: &Self) -> bool {
+            self.0.disjoint_from(&other.0)
+        }
+
+        #[pyo3(signature = (other, atol=1e-10))]
+        #[pyo3(text_signature = "(other: QuantumGate, atol: float = 1e-10)")]
+        fn is_inverse_of(&self, other: &Self, atol: f64) -> bool {
+            self.0.is_inverse_of(&other.0, atol)
+        }
+
+        #[pyo3(signature = (other, atol=1e-10))]
+        #[pyo3(text_signature = "(other: QuantumGate, atol: float = 1e-10)")]
+        fn commutes_with(&self, other: &Self, atol: f64) -> PyResult<bool> {
+            self.0.commutes_with(&other.0, atol)
+        }
+
+        fn is_clifford(&self) -> bool {
+            self.0.is_clifford()
+        }
+
+        fn measurement_pairs(&self) -> PyResult<Vec<(usize, usize)>> {
+            self.0.measurement_pairs()
+        }
+
+        fn diagonalizing_circuit(&self) -> PyResult<Vec<QuantumGate>> {
+            self.0.diagonalizing_circuit()
+        }
+
+        fn is_measurement(&self) -> bool {
+            matches!(self.0, QuantumGate::Measurement(_, _))
+        }
+
+        fn propagate_pauli(
+            &self,
+            pauli_ids: Vec<u8>,
+            qubits: Vec<usize>,
+        ) -> PyResult<(Vec<usize>, Vec<u8>, i8)> {
+            self.0.propagate_pauli(&pauli_ids, &qubits)
+        }
+
+        #[pyo3(signature = (other, atol=1e-10))]
+        #[pyo3(text_signature = "(other: QuantumGate, atol: float = 1e-10)")]
+        fn equiv_up_to_global_phase(&self, other: &Self, atol: f64) -> PyResult<bool> {
+            self.0.equiv_up_to_global_phase(&other.0, atol)
+        }
+
+        #[pyo3(signature = (atol=1e-10))]
+        #[pyo3(text_signature = "(atol: float = 1e-10)")]
+        fn is_unitary(&self, atol: f64) -> bool {
+            self.0.is_unitary(atol)
+        }
+
+        fn controlled(&self, control_index: usize) -> PyResult<QuantumGate> {
+            self.0.controlled(control_index)
+        }
+
+        /// The gate with the given controls removed, assuming they are in |1>.
+        /// Raises if a qubit is not a control of the gate.
+        #[pyo3(text_signature = "(assume_control_set: Sequence[int])")]
+        fn uncontrol(&self, assume_control_set: Vec<usize>) -> PyResult<QuantumGate> {
+            self.0.uncontrol(&assume_control_set)
+        }
+
+        fn inverse(&self) -> PyResult<QuantumGate> {
+            self.0.inverse()
+        }
+
+        fn power(&self, n: i64) -> PyResult<QuantumGate> {
+            self.0.power(n)
+        }
+
+        #[pyo3(signature = (strict=false))]
+        #[pyo3(text_signature = "(strict: bool = False)")]
+        fn pauli_string(&self, strict: bool) -> PyResult<String> {
+            self.0.pauli_string(strict)
+        }
+
+        fn angle(&self) -> PyResult<f64> {
+            self.0.angle()
+        }
+
+        fn angle_degrees(&self) -> PyResult<f64> {
+            self.0.angle_degrees()
+        }
+
+        /// The same gate carrying a duration of `ns` nanoseconds. The duration does
+        /// not take part in equality or hashing.
+        fn with_duration(&self, ns: f64) -> PyResult<Self> {
+            if !(ns.is_finite() && ns >= 0.0) {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "The duration of a gate must be a non-negative number of nanoseconds, got {}.",
+                    ns
+                )));
+            }
+            Ok(Self(self.0.clone(), self.1.clone(), Some(ns)))
+        }
+
+        #[getter]
+        fn get_duration(&self) -> Option<f64> {
+            self.2
+        }
+
+        fn normalized(&self) -> QuantumGate {
+            self.0.normalized()
+        }
+
+        /// Phase in radians the gate carries beyond the rotation it is usually
+        /// written as, e.g. pi/4 for S = exp(i pi/4) RZ(pi/2), or 0 for gates
+        /// without one.
+        fn global_phase(&self) -> f64 {
+            self.0.global_phase()
+        }
+
+        /// An equal gate with symmetric indices in ascending order, so that e.g.
+        /// `CZ(1, 0).canonicalize() == CZ(0, 1)`.
+        fn canonicalize(&self) -> QuantumGate {
+            self.0.canonicalize()
+        }
+
+        fn scale_params(&self, factor: f64) -> PyResult<QuantumGate> {
+            self.0.scale_params(factor)
+        }
+
+        fn as_parametric(&self) -> PyResult<ParametricQuantumGate> {
+            self.0.as_parametric()
+        }
+
+        #[pyo3(signature = (mapping, classical_mapping=None, strict=false))]
+        #[pyo3(
+            text_signature = "(mapping: Mapping[int, int], classical_mapping: Optional[Mapping[int, int]] = None, strict: bool = False)"
+        )]
+        fn remap_qubits(
+            &self,
+            mapping: HashMap<usize, usize>,
+            classical_mapping: Option<HashMap<usize, usize>>,
+            strict: bool,
+        ) -> PyResult<QuantumGate> {
+            self.0
+                .remap_qubits(&mapping, classical_mapping.as_ref(), strict)
+        }
+
+        fn decompose(&self) -> Vec<QuantumGate> {
+            self.0.decompose()
+        }
+
+        /// With `endian="little"`, the default, the first qubit of `get_qubits()` is
+        /// the least significant bit of the basis index; `"big"` makes it the most
+        /// significant one.
+        #[pyo3(signature = (endian="little"))]
+        #[pyo3(text_signature = "(endian: Literal[\"big\", \"little\"] = \"little\")")]
+        fn matrix<'py>(slf: &Bound<'py, Self>, endian: &str) -> PyResult<Bound<'py, PyTuple>> {
+            let mat = slf.get().0.matrix()?;
+            let mat = match endian {
+                "little" => mat,
+                "big" => matrix::reverse_bit_order(&mat),
+                _ => {
+                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "endian must be 'big' or 'little', got '{}'.",
+                        endian
+                    )))
+                }
+            };
+            Ok(matrix_to_py(slf.py(), mat))
+        }
+
+        fn with_swapped_order(&self) -> PyResult<QuantumGate> {
+            self.0.with_swapped_order()
+        }
+
+        fn to_matrix_gate(&self) -> PyResult<QuantumGate> {
+            self.0.to_matrix_gate()
+        }
+
+        fn expected_matrix<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyTuple>> {
+            Ok(matrix_to_py(slf.py(), slf.get().0.expected_matrix()?))
+        }
+
+        /// The Hermitian generator H of rotation gates, with the gate equal to
+        /// `exp(-iH)` on its qubits in the `matrix()` order.
+        fn generator<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyTuple>> {
+            Ok(matrix_to_py(slf.py(), slf.get().0.generator()?))
+        }
+
+        fn to_qasm(&self) -> PyResult<String> {
+            self.0.to_qasm()
+        }
+
+        fn to_qasm3(&self) -> PyResult<String> {
+            self.0.to_qasm3()
+        }
+
+        fn to_cirq_spec(&self) -> PyResult<(&'static str, Vec<usize>, Vec<f64>)> {
+            self.0.to_cirq_spec()
+        }
+
+        fn to_stim(&self) -> PyResult<String> {
+            self.0.to_stim()
+        }
+
+        #[classmethod]
+        fn from_qasm(_cls: &Bound<'_, PyType>, line: &str) -> PyResult<QuantumGate> {
+            QuantumGate::from_qasm(line)
+        }
+
+        #[classmethod]
+        #[pyo3(signature = (name, qubits, params=vec![]))]
+        #[pyo3(text_signature = "(name: str, qubits: Sequence[int], params: Sequence[float] = ())")]
+        fn from_qiskit(
+            _cls: &Bound<'_, PyType>,
+            name: &str,
+            qubits: Vec<usize>,
+            params: Vec<f64>,
+        ) -> PyResult<QuantumGate> {
+            QuantumGate::from_qiskit(name, &qubits, &params)
+        }
+
+        /// The fields of the gate as a dict keyed by the constructor argument names.
+        /// `unitary_matrix` is None for gates without a matrix.
+        fn as_dict<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyDict>> {
+            let py = slf.py();
+            let prop = slf.get().property();
+            let dict = PyDict::new_bound(py);
+            dict.set_item("name", &prop.name)?;
+            dict.set_item(
+                "target_indices",
+                PyTuple::new_bound(py, &prop.target_indices),
+            )?;
+            dict.set_item(
+                "control_indices",
+                PyTuple::new_bound(py, &prop.control_indices),
+            )?;
+            dict.set_item(
+                "classical_indices",
+                PyTuple::new_bound(py, &prop.classical_indices),
+            )?;
+            dict.set_item("params", PyTuple::new_bound(py, &prop.params))?;
+            dict.set_item("pauli_ids", PyTuple::new_bound(py, &prop.pauli_ids))?;
+            dict.set_item(
+                "unitary_matrix",
+                prop.unitary_matrix.clone().map(|mat| matrix_to_py(py, mat)),
+            )?;
+            Ok(dict)
+        }
+
+        /// Inverse of `as_dict`. `name` and `target_indices` are required and the
+        /// other keys default as in the constructor.
+        #[classmethod]
+        fn from_dict(_cls: &Bound<'_, PyType>, d: &Bound<'_, PyDict>) -> PyResult<Self> {
+            const KEYS: [&str; 7] = [
+                "name",
+                "target_indices",
+                "control_indices",
+                "classical_indices",
+                "params",
+                "pauli_ids",
+                "unitary_matrix",
+            ];
+            for key in d.keys() {
+                let key: String = key.extract()?;
+                if !KEYS.contains(&key.as_str()) {
+                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "Unknown key '{}' in gate dict.",
+                        key
+                    )));
+                }
+            }
+            let required = |key: &str| {
+                d.get_item(key)?.ok_or_else(|| {
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "Missing key '{}' in gate dict.",
+                        key
+                    ))
+                })
+            };
+            fn optional<'py, T: FromPyObject<'py> + Default>(
+                d: &Bound<'py, PyDict>,
+                key: &str,
+            ) -> PyResult<T> {
+                match d.get_item(key)? {
+                    Some(v) if !v.is_none() => v.extract(),
+                    _ => Ok(T::default()),
+                }
+            }
+            Self::py_new(
+                required("name")?.extract()?,
+                required("target_indices")?.extract()?,
+                optional(d, "control_indices")?,
+                optional(d, "classical_indices")?,
+                optional(d, "params")?,
+                optional(d, "pauli_ids")?,
+                optional(d, "unitary_matrix")?,
+            )
+        }
+
+        /// UnitaryMatrix gate from a flat row-major sequence of `dim * dim` entries.
+        #[classmethod]
+        fn unitary_from_buffer(
+            _cls: &Bound<'_, PyType>,
+            target_indices: Vec<usize>,
+            flat: Vec<Complex64>,
+            dim: usize,
+        ) -> PyResult<QuantumGate> {
+            crate::circuit::gates::unitary_matrix_from_buffer(target_indices, flat, dim)
+        }
+
+        fn to_bytes<'py>(slf: &Bound<'py, Self>) -> Bound<'py, PyBytes> {
+            PyBytes::new_bound(slf.py(), &slf.get().property().to_bytes())
+        }
+
+        #[classmethod]
+        fn from_bytes(_cls: &Bound<'_, PyType>, data: &[u8]) -> PyResult<Self> {
+            let prop = GenericGateProperty::from_bytes(data)?;
+            Self::py_new(
+                prop.name,
+                prop.target_indices,
+                prop.control_indices,
+                prop.classical_indices,
+                prop.params,
+                prop.pauli_ids,
+                prop.unitary_matrix,
+            )
+        }
+
+        fn pretty(&self) -> String {
+            self.property().pretty()
+        }
+
+        /// Key for sorting gates deterministically, e.g. `sorted(gates,
+        /// key=QuantumGate.sort_key)`: `(name, min(target_indices), target_indices,
+        /// control_indices)`, with -1 as the minimum of empty targets. Every field
+        /// is a str, int or tuple of ints, so any two keys compare.
+        fn sort_key<'py>(
+            slf: &Bound<'py, Self>,
+        ) -> (String, i64, Bound<'py, PyTuple>, Bound<'py, PyTuple>) {
+            let (name, min_target, targets, controls) = slf.get().property().sort_key();
+            (
+                name.to_owned(),
+                min_target,
+                PyTuple::new_bound(slf.py(), targets),
+                PyTuple::new_bound(slf.py(), controls),
+            )
+        }
+        fn canonical_repr(&self) -> String {
+            self.property().canonical_repr()
+        }
+
+        fn content_hash(&self) -> u128 {
+            self.property().content_hash()
+        }
+
+        fn to_json(&self) -> PyResult<String> {
+            self.property().to_json()
+        }
+
+        #[classmethod]
+        fn from_json(_cls: &Bound<'_, PyType>, s: &str) -> PyResult<Self> {
+            let prop = GenericGateProperty::from_json(s)?;
+            Self::py_new(
+                prop.name,
+                prop.target_indices,
+                prop.control_indices,
+                prop.classical_indices,
+                prop.params,
+                prop.pauli_ids,
+                prop.unitary_matrix,
+            )
         }
 
         #[getter]
         fn get_name(&self) -> String {
-            self.0.clone().into_property().name.into()
+            self.property().name.clone()
+        }
+
+        #[getter]
+        fn get_kind(&self) -> &'static str {
+            self.0.kind()
+        }
+
+        #[getter]
+        fn get_matrix_array<'py>(slf: &Bound<'py, Self>) -> PyResult<Option<Bound<'py, PyAny>>> {
+            let Some(mat) = &slf.get().property().unitary_matrix else {
+                return Ok(None);
+            };
+            let py = slf.py();
+            let numpy = py.import_bound("numpy")?;
+            let kwargs = PyDict::new_bound(py);
+            kwargs.set_item("dtype", numpy.getattr("complex128")?)?;
+            let rows = PyTuple::new_bound(py, mat.iter().map(|row| PyTuple::new_bound(py, row)));
+            Ok(Some(numpy.call_method("array", (rows,), Some(&kwargs))?))
+        }
+
+        #[getter]
+        fn get_classical_bit_count(&self) -> usize {
+            let mut cbits = self.0.get_cbits();
+            cbits.sort_unstable();
+            cbits.dedup();
+            cbits.len()
+        }
+
+        #[getter]
+        fn get_qubit_count(&self) -> usize {
+            self.0.sorted_qubits().len()
         }
 
         #[getter]
         fn get_target_indices<'py>(slf: &Bound<'py, Self>) -> Bound<'py, PyTuple> {
-            let v: Vec<usize> = slf.get().0.clone().into_property().target_indices.into();
+            let v: Vec<usize> = slf.get().property().target_indices.clone();
             PyTuple::new_bound(slf.py(), v)
         }
 
         #[getter]
         fn get_control_indices<'py>(slf: &Bound<'py, Self>) -> Bound<'py, PyTuple> {
-            let v: Vec<usize> = slf.get().0.clone().into_property().control_indices.into();
+            let v: Vec<usize> = slf.get().property().control_indices.clone();
             PyTuple::new_bound(slf.py(), v)
         }
 
         #[getter]
         fn get_classical_indices<'py>(slf: &Bound<'py, Self>) -> Bound<'py, PyTuple> {
-            let v: Vec<usize> = slf.get().0.clone().into_property().classical_indices.into();
+            let v: Vec<usize> = slf.get().property().classical_indices.clone();
             PyTuple::new_bound(slf.py(), v)
         }
 
         #[getter]
         fn get_params<'py>(slf: &Bound<'py, Self>) -> Bound<'py, PyTuple> {
-            let v: Vec<f64> = slf.get().0.clone().into_property().params.into();
+            let v: Vec<f64> = slf.get().property().params.clone();
             PyTuple::new_bound(slf.py(), v)
         }
 
         #[getter]
         fn get_pauli_ids<'py>(slf: &Bound<'py, Self>) -> Bound<'py, PyTuple> {
-            let v: Vec<u8> = slf.get().0.clone().into_property().pauli_ids.into();
+            let v: Vec<u8> = slf.get().property().pauli_ids.clone();
             PyTuple::new_bound(slf.py(), v)
         }
 
         #[getter]
         fn get_unitary_matrix<'py>(slf: &Bound<'py, Self>) -> Bound<'py, PyTuple> {
-            if let Some(mat) = slf.get().0.clone().into_property().unitary_matrix {
+            if let Some(mat) = &slf.get().property().unitary_matrix {
                 let mat = mat
-                    .into_iter()
+                    .iter()
                     .map(|row| {
-                        row.into_iter()
-                            .map(|c| {
+                        row.iter()
+                            .map(|&c| {
                                 // The Rust version of initialization of UnitaryMatrix gate treats all elements
                                 // of the matrix as `complex` (not `float`). It may cause some
                                 // backward-noncompatibility with Python codes which give `float` values
@@ -826,6 +2522,96 @@ mod wrapper {
         }
     }
 
+    pub(crate) fn matrix_to_py(py: Python<'_>, mat: matrix::Matrix) -> Bound<'_, PyTuple> {
+        PyTuple::new_bound(py, mat.into_iter().map(|row| PyTuple::new_bound(py, row)))
+    }
+
+    /// Builds many gates in one call from parallel sequences, validating each gate
+    /// as the `QuantumGate` constructor does. Omitted sequences default to empty
+    /// indices or parameters for every gate.
+    #[pyfunction]
+    #[pyo3(
+        signature = (names, target_indices_list, params_list=None, control_indices_list=None, classical_indices_list=None, pauli_ids_list=None),
+        text_signature = "(
+            names: Sequence[str],
+            target_indices_list: Sequence[Sequence[int]],
+            params_list: Optional[Sequence[Sequence[float]]] = None,
+            control_indices_list: Optional[Sequence[Sequence[int]]] = None,
+            classical_indices_list: Optional[Sequence[Sequence[int]]] = None,
+            pauli_ids_list: Optional[Sequence[Sequence[int]]] = None
+        )"
+    )]
+    pub fn gates_from_arrays(
+        names: Vec<String>,
+        target_indices_list: Vec<Vec<usize>>,
+        mut params_list: Option<Vec<Vec<f64>>>,
+        mut control_indices_list: Option<Vec<Vec<usize>>>,
+        mut classical_indices_list: Option<Vec<Vec<usize>>>,
+        mut pauli_ids_list: Option<Vec<Vec<u8>>>,
+    ) -> PyResult<Vec<QuantumGate<f64>>> {
+        let count = names.len();
+        for (label, len) in [
+            ("target_indices_list", Some(target_indices_list.len())),
+            ("params_list", params_list.as_ref().map(Vec::len)),
+            (
+                "control_indices_list",
+                control_indices_list.as_ref().map(Vec::len),
+            ),
+            (
+                "classical_indices_list",
+                classical_indices_list.as_ref().map(Vec::len),
+            ),
+            ("pauli_ids_list", pauli_ids_list.as_ref().map(Vec::len)),
+        ] {
+            if let Some(len) = len.filter(|len| *len != count) {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "{} has {} entries but {} names were given.",
+                    label, len, count
+                )));
+            }
+        }
+        fn nth<T>(list: &mut Option<Vec<Vec<T>>>, i: usize) -> Vec<T> {
+            list.as_mut()
+                .map(|l| std::mem::take(&mut l[i]))
+                .unwrap_or_default()
+        }
+        names
+            .into_iter()
+            .zip(target_indices_list)
+            .enumerate()
+            .map(|(i, (name, target_indices))| {
+                QuantumGateWrapper::py_new(
+                    name,
+                    target_indices,
+                    nth(&mut control_indices_list, i),
+                    nth(&mut classical_indices_list, i),
+                    nth(&mut params_list, i),
+                    nth(&mut pauli_ids_list, i),
+                    None,
+                )
+                .map(|gate| gate.0)
+            })
+            .collect()
+    }
+
+    /// Whether `gate` is a `ParametricQuantumGate` rather than a `QuantumGate`,
+    /// including instances of Python subclasses. Raises `TypeError` for anything
+    /// else.
+    #[pyfunction]
+    #[pyo3(text_signature = "(gate: Union[QuantumGate, ParametricQuantumGate])")]
+    pub fn is_parametric(gate: &Bound<'_, PyAny>) -> PyResult<bool> {
+        if gate.is_instance_of::<ParametricQuantumGate>() {
+            Ok(true)
+        } else if gate.is_instance_of::<QuantumGateWrapper>() {
+            Ok(false)
+        } else {
+            Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                "Expected QuantumGate or ParametricQuantumGate, got {}.",
+                gate.get_type().name()?
+            )))
+        }
+    }
+
     pub fn add_quantum_gate(m: &Bound<'_, PyModule>) -> PyResult<()> {
         m.add_class::<QuantumGateWrapper>()?;
         Ok(())
@@ -836,5 +2622,30 @@ pub fn py_module<'py>(py: Python<'py>) -> PyResult<Bound<'py, PyModule>> {
     let m = PyModule::new_bound(py, "gate")?;
     m.add_class::<ParametricQuantumGate>()?;
     wrapper::add_quantum_gate(&m)?;
+    m.add("UnknownGateError", py.get_type_bound::<UnknownGateError>())?;
+    m.add(
+        "InvalidUnitaryError",
+        py.get_type_bound::<InvalidUnitaryError>(),
+    )?;
+    m.add(
+        "IndexConflictError",
+        py.get_type_bound::<IndexConflictError>(),
+    )?;
+    m.add_wrapped(wrap_pyfunction!(matrix::fuse_single_qubit))?;
+    m.add_wrapped(wrap_pyfunction!(matrix::tensor))?;
+    m.add_wrapped(wrap_pyfunction!(matrix::matrices))?;
+    m.add_wrapped(wrap_pyfunction!(matrix::unitary_u1))?;
+    m.add_wrapped(wrap_pyfunction!(matrix::unitary_u2))?;
+    m.add_wrapped(wrap_pyfunction!(matrix::unitary_u3))?;
+    m.add_wrapped(wrap_pyfunction!(statevector::apply_gate))?;
+    m.add_wrapped(wrap_pyfunction!(decompose::inverse_sequence))?;
+    m.add_wrapped(wrap_pyfunction!(diff::gate_diff))?;
+    m.add_wrapped(wrap_pyfunction!(merge::merge_rotations))?;
+    m.add_wrapped(wrap_pyfunction!(resource::count_by_name))?;
+    m.add_wrapped(wrap_pyfunction!(resource::t_count))?;
+    m.add_wrapped(wrap_pyfunction!(resource::two_qubit_count))?;
+    m.add_wrapped(wrap_pyfunction!(schema::supported_gates))?;
+    m.add_wrapped(wrap_pyfunction!(wrapper::gates_from_arrays))?;
+    m.add_wrapped(wrap_pyfunction!(wrapper::is_parametric))?;
     Ok(m)
 }
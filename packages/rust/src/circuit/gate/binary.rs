@@ -0,0 +1,177 @@
+use crate::circuit::gate::GenericGateProperty;
+use num_complex::Complex64;
+use pyo3::prelude::*;
+
+/// Leading bytes of every encoded gate: a magic number followed by the format version.
+const MAGIC: &[u8; 3] = b"QPG";
+const VERSION: u8 = 1;
+
+fn write_varint(out: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        out.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
+
+fn write_f64(out: &mut Vec<u8>, v: f64) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(n).filter(|e| *e <= self.data.len());
+        let end = end.ok_or_else(|| "unexpected end of data".to_owned())?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut v = 0u64;
+        for shift in (0..64).step_by(7) {
+            let b = self.take(1)?[0];
+            v |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Ok(v);
+            }
+        }
+        Err("varint is too long".to_owned())
+    }
+
+    fn usize(&mut self) -> Result<usize, String> {
+        usize::try_from(self.varint()?).map_err(|e| e.to_string())
+    }
+
+    fn f64(&mut self) -> Result<f64, String> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn indices(&mut self) -> Result<Vec<usize>, String> {
+        let len = self.usize()?;
+        (0..len).map(|_| self.usize()).collect()
+    }
+}
+
+impl GenericGateProperty {
+    /// Encodes the property as `MAGIC`, `VERSION` and the fields in declaration
+    /// order. Lengths and indices are LEB128 varints and floats little-endian f64.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        out.push(VERSION);
+        write_varint(&mut out, self.name.len() as u64);
+        out.extend_from_slice(self.name.as_bytes());
+        for indices in [
+            &self.target_indices,
+            &self.control_indices,
+            &self.classical_indices,
+        ] {
+            write_varint(&mut out, indices.len() as u64);
+            for i in indices {
+                write_varint(&mut out, *i as u64);
+            }
+        }
+        write_varint(&mut out, self.params.len() as u64);
+        for p in &self.params {
+            write_f64(&mut out, *p);
+        }
+        write_varint(&mut out, self.pauli_ids.len() as u64);
+        out.extend_from_slice(&self.pauli_ids);
+        match &self.unitary_matrix {
+            Some(mat) => {
+                out.push(1);
+                write_varint(&mut out, mat.len() as u64);
+                for row in mat {
+                    write_varint(&mut out, row.len() as u64);
+                    for c in row {
+                        write_f64(&mut out, c.re);
+                        write_f64(&mut out, c.im);
+                    }
+                }
+            }
+            None => out.push(0),
+        }
+        out
+    }
+
+    pub fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Self::read_bytes(data).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("Invalid gate data: {}", e))
+        })
+    }
+
+    fn read_bytes(data: &[u8]) -> Result<Self, String> {
+        let mut r = Reader { data, pos: 0 };
+        if r.take(MAGIC.len()).ok() != Some(&MAGIC[..]) {
+            return Err("missing magic number".to_owned());
+        }
+        let version = r.take(1)?[0];
+        if version != VERSION {
+            return Err(format!("unsupported version {}", version));
+        }
+        let name_len = r.usize()?;
+        let name = String::from_utf8(r.take(name_len)?.to_vec()).map_err(|e| e.to_string())?;
+        let target_indices = r.indices()?;
+        let control_indices = r.indices()?;
+        let classical_indices = r.indices()?;
+        let params_len = r.usize()?;
+        let params = (0..params_len).map(|_| r.f64()).collect::<Result<_, _>>()?;
+        let pauli_len = r.usize()?;
+        let pauli_ids = r.take(pauli_len)?.to_vec();
+        let unitary_matrix = match r.take(1)?[0] {
+            0 => None,
+            1 => {
+                let rows = r.usize()?;
+                let mut mat = Vec::with_capacity(rows.min(data.len()));
+                for _ in 0..rows {
+                    let cols = r.usize()?;
+                    let row = (0..cols)
+                        .map(|_| Ok(Complex64::new(r.f64()?, r.f64()?)))
+                        .collect::<Result<Vec<_>, String>>()?;
+                    mat.push(row);
+                }
+                Some(mat)
+            }
+            flag => return Err(format!("invalid matrix flag {}", flag)),
+        };
+        if r.pos != data.len() {
+            return Err("trailing bytes".to_owned());
+        }
+        Ok(GenericGateProperty {
+            name,
+            target_indices,
+            control_indices,
+            classical_indices,
+            params,
+            pauli_ids,
+            unitary_matrix,
+        })
+    }
+}
+
+#[test]
+fn test_bytes_roundtrip() {
+    let prop = GenericGateProperty {
+        name: "UnitaryMatrix".to_owned(),
+        target_indices: vec![0, 300],
+        control_indices: vec![],
+        classical_indices: vec![],
+        params: vec![],
+        pauli_ids: vec![],
+        unitary_matrix: Some(vec![
+            vec![Complex64::new(0.0, 1.0), Complex64::new(-0.0, 0.5)],
+            vec![Complex64::new(1e-300, 0.0), Complex64::new(0.25, -1.0)],
+        ]),
+    };
+    let data = prop.to_bytes();
+    assert_eq!(&data[..4], b"QPG\x01");
+    let parsed = GenericGateProperty::read_bytes(&data).unwrap();
+    assert_eq!(parsed.target_indices, prop.target_indices);
+    assert_eq!(parsed.unitary_matrix, prop.unitary_matrix);
+    assert!(GenericGateProperty::read_bytes(&data[..data.len() - 1]).is_err());
+}
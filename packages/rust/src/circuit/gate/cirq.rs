@@ -0,0 +1,61 @@
+use crate::circuit::gate::QuantumGate;
+use pyo3::prelude::*;
+use std::f64::consts::PI;
+
+impl QuantumGate<f64> {
+    /// Returns `(name, qubits, params)` naming the Cirq gate, with the qubits in
+    /// `get_qubits()` order. How the gate is built from `params` depends on `name`:
+    ///
+    /// - Without params, `getattr(cirq, name)` is a gate constant such as `cirq.CX`,
+    ///   applied with `.on(*qubits)`.
+    /// - `rx`, `ry` and `rz` take the angle: `getattr(cirq, name)(*params)`.
+    /// - The `*PowGate` classes take the single param as their keyword-only exponent:
+    ///   `getattr(cirq, name)(exponent=params[0])`.
+    ///
+    /// | Gate                            | Cirq                                 |
+    /// |---------------------------------|--------------------------------------|
+    /// | Identity                        | `I`                                  |
+    /// | X, Y, Z, H, S, T                | same name                            |
+    /// | Sdag, Tdag                      | `ZPowGate(-1/2)`, `ZPowGate(-1/4)`   |
+    /// | SqrtX(dag), SqrtY(dag)          | `XPowGate(±1/2)`, `YPowGate(±1/2)`   |
+    /// | RX, RY, RZ                      | `rx`, `ry`, `rz` with the angle      |
+    /// | U1(lmd)                         | `ZPowGate(lmd / pi)`                 |
+    /// | CNOT, CZ, SWAP, ISWAP           | `CX`, `CZ`, `SWAP`, `ISWAP`          |
+    /// | SqrtISWAP, TOFFOLI              | `SQRT_ISWAP`, `CCX`                  |
+    ///
+    /// Other gates have no Cirq equivalent of this form.
+    pub fn to_cirq_spec(&self) -> PyResult<(&'static str, Vec<usize>, Vec<f64>)> {
+        let (name, params) = match self {
+            Self::Identity(_) => ("I", vec![]),
+            Self::X(_) => ("X", vec![]),
+            Self::Y(_) => ("Y", vec![]),
+            Self::Z(_) => ("Z", vec![]),
+            Self::H(_) => ("H", vec![]),
+            Self::S(_) => ("S", vec![]),
+            Self::Sdag(_) => ("ZPowGate", vec![-0.5]),
+            Self::T(_) => ("T", vec![]),
+            Self::Tdag(_) => ("ZPowGate", vec![-0.25]),
+            Self::SqrtX(_) => ("XPowGate", vec![0.5]),
+            Self::SqrtXdag(_) => ("XPowGate", vec![-0.5]),
+            Self::SqrtY(_) => ("YPowGate", vec![0.5]),
+            Self::SqrtYdag(_) => ("YPowGate", vec![-0.5]),
+            Self::RX(_, p) => ("rx", vec![*p]),
+            Self::RY(_, p) => ("ry", vec![*p]),
+            Self::RZ(_, p) => ("rz", vec![*p]),
+            Self::U1(_, lmd) => ("ZPowGate", vec![lmd / PI]),
+            Self::CNOT(_, _) => ("CX", vec![]),
+            Self::CZ(_, _) => ("CZ", vec![]),
+            Self::SWAP(_, _) => ("SWAP", vec![]),
+            Self::ISWAP(_, _) => ("ISWAP", vec![]),
+            Self::SqrtISWAP(_, _) => ("SQRT_ISWAP", vec![]),
+            Self::TOFFOLI(_, _, _) => ("CCX", vec![]),
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "{} gate does not have a Cirq equivalent.",
+                    &self.clone().into_property().name
+                )))
+            }
+        };
+        Ok((name, self.get_qubits(), params))
+    }
+}
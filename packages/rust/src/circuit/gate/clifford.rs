@@ -0,0 +1,238 @@
+use crate::circuit::gate::matrix::{self, Matrix};
+use crate::circuit::gate::QuantumGate;
+use num_complex::Complex64;
+use pyo3::prelude::*;
+use std::f64::consts::FRAC_PI_2;
+
+/// Decomposes `mat` as `coef * P` for a Pauli string `P`, returning the Pauli ids
+/// (the first one acting on the lowest bit) and `coef`.
+fn pauli_decomposition(mat: &Matrix, atol: f64) -> Option<(Vec<u8>, Complex64)> {
+    let dim = mat.len();
+    let n = dim.trailing_zeros();
+    // X^x Z^z maps each basis state c to c ^ x with a phase (-1)^(z . c).
+    let x = mat[0].iter().position(|v| v.norm() > atol)?;
+    let phase = mat[0][x];
+    let z = (0..n)
+        .filter(|k| (mat[1 << k][(1 << k) ^ x] / phase).re < 0.0)
+        .fold(0, |acc, k| acc | 1 << k);
+    let is_pauli = (0..dim).all(|r| {
+        let sign = if (z & r).count_ones() % 2 == 0 {
+            1.0
+        } else {
+            -1.0
+        };
+        (0..dim).all(|c| {
+            let expected = if c == r ^ x { phase * sign } else { 0.0.into() };
+            (mat[r][c] - expected).norm() <= atol
+        })
+    });
+    if !is_pauli {
+        return None;
+    }
+    // Y = i X Z.
+    let ids: Vec<u8> = (0..n)
+        .map(|k| match ((x >> k) & 1, (z >> k) & 1) {
+            (0, 0) => 0,
+            (1, 0) => 1,
+            (1, 1) => 2,
+            _ => 3,
+        })
+        .collect();
+    let y_count = ids.iter().filter(|p| **p == 2).count() as i32;
+    let x_z_sign = if (z & x).count_ones() % 2 == 0 {
+        1.0
+    } else {
+        -1.0
+    };
+    Some((ids, phase / (x_z_sign * Complex64::i().powi(y_count))))
+}
+
+fn is_pauli_up_to_phase(mat: &Matrix, atol: f64) -> bool {
+    pauli_decomposition(mat, atol).is_some_and(|(_, coef)| (coef.norm() - 1.0).abs() <= atol)
+}
+
+/// Whether the unitary `mat` maps every Pauli string to a Pauli string under
+/// conjugation, which is checked on the generators X_k and Z_k.
+pub fn is_clifford_matrix(mat: &Matrix, atol: f64) -> bool {
+    let n = mat.len().trailing_zeros() as usize;
+    if mat.len() != 1 << n {
+        return false;
+    }
+    let dagger = matrix::dagger(mat);
+    (0..n).all(|k| {
+        [1, 3].iter().all(|&p| {
+            let mut ids = vec![0; n];
+            ids[k] = p;
+            let conjugated =
+                matrix::matmul(&matrix::matmul(mat, &matrix::pauli_string(&ids)), &dagger);
+            is_pauli_up_to_phase(&conjugated, atol)
+        })
+    })
+}
+
+fn is_multiple_of_half_pi(angle: f64) -> bool {
+    let k = angle / FRAC_PI_2;
+    (k - k.round()).abs() < 1e-9
+}
+
+impl QuantumGate<f64> {
+    /// Rotation gates are Clifford when their angle is a multiple of pi/2. U gates
+    /// and matrix gates are checked numerically on their matrices.
+    pub fn is_clifford(&self) -> bool {
+        const ATOL: f64 = 1e-9;
+        match self.kind() {
+            "Pauli" | "Clifford" => true,
+            "NonClifford" | "Measurement" => false,
+            _ => match self {
+                Self::RX(_, p) | Self::RY(_, p) | Self::RZ(_, p) => is_multiple_of_half_pi(*p),
+                Self::PauliRotation(_, _, p) => is_multiple_of_half_pi(*p),
+                Self::Other(o) => o.unitary_matrix.as_ref().is_some_and(|mat| {
+                    matrix::is_unitary(mat, ATOL) && is_clifford_matrix(mat, ATOL)
+                }),
+                _ => self.try_matrix().is_some_and(|mat| {
+                    matrix::is_unitary(&mat, ATOL) && is_clifford_matrix(&mat, ATOL)
+                }),
+            },
+        }
+    }
+
+    /// Conjugates the Pauli string `pauli_ids` on `qubits` by the gate, i.e. computes
+    /// `U P U^dag`. Returns the qubits (the given ones followed by the other qubits of
+    /// the gate), the Pauli ids on them and the sign of the result.
+    pub fn propagate_pauli(
+        &self,
+        pauli_ids: &[u8],
+        qubits: &[usize],
+    ) -> PyResult<(Vec<usize>, Vec<u8>, i8)> {
+        if pauli_ids.len() != qubits.len() {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "pauli_ids and qubits must have the same length.",
+            ));
+        }
+        if pauli_ids.iter().any(|p| *p > 3) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Pauli ids must be 0 (I), 1 (X), 2 (Y) or 3 (Z).",
+            ));
+        }
+        crate::circuit::gates::check_distinct_indices("Pauli", qubits)?;
+        let not_clifford = || {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "Pauli operators cannot be propagated through non-Clifford {} gate.",
+                &self.clone().into_property().name
+            ))
+        };
+        if !self.is_clifford() {
+            return Err(not_clifford());
+        }
+        let mat = self.try_matrix().ok_or_else(not_clifford)?;
+        let gate_qubits = self.get_qubits();
+        let local_ids: Vec<u8> = gate_qubits
+            .iter()
+            .map(|q| {
+                qubits
+                    .iter()
+                    .position(|p| p == q)
+                    .map_or(0, |i| pauli_ids[i])
+            })
+            .collect();
+        let conjugated = matrix::matmul(
+            &matrix::matmul(&mat, &matrix::pauli_string(&local_ids)),
+            &matrix::dagger(&mat),
+        );
+        let (new_ids, coef) = pauli_decomposition(&conjugated, 1e-9).ok_or_else(not_clifford)?;
+        let mut out_qubits = qubits.to_vec();
+        let mut out_ids = pauli_ids.to_vec();
+        for (q, p) in gate_qubits.iter().zip(new_ids) {
+            match qubits.iter().position(|r| r == q) {
+                Some(i) => out_ids[i] = p,
+                None => {
+                    out_qubits.push(*q);
+                    out_ids.push(p);
+                }
+            }
+        }
+        Ok((out_qubits, out_ids, if coef.re < 0.0 { -1 } else { 1 }))
+    }
+}
+
+/// Gates, applied in order, that rotate the Pauli `pauli_ids` on `qubits` into a
+/// product of Z and I: H for X, and Sdag followed by H for Y.
+fn diagonalizing_gates(qubits: &[usize], pauli_ids: &[u8]) -> Vec<QuantumGate> {
+    qubits
+        .iter()
+        .zip(pauli_ids)
+        .flat_map(|(q, p)| match p {
+            1 => vec![QuantumGate::H(*q)],
+            2 => vec![QuantumGate::Sdag(*q), QuantumGate::H(*q)],
+            _ => vec![],
+        })
+        .collect()
+}
+
+impl QuantumGate<f64> {
+    /// Basis-change gates, applied in order, after which measuring in the Z basis
+    /// measures the Pauli operator of the gate. PauliRotation gates use the Pauli
+    /// they rotate around, and a Measurement is already in the Z basis.
+    pub fn diagonalizing_circuit(&self) -> PyResult<Vec<Self>> {
+        match self {
+            Self::X(q) => Ok(diagonalizing_gates(&[*q], &[1])),
+            Self::Y(q) => Ok(diagonalizing_gates(&[*q], &[2])),
+            Self::Z(_) | Self::Measurement(_, _) => Ok(vec![]),
+            Self::Pauli(qs, ps) | Self::PauliRotation(qs, ps, _) => Ok(diagonalizing_gates(qs, ps)),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "{} gate is neither a Pauli gate nor a measurement.",
+                &self.clone().into_property().name
+            ))),
+        }
+    }
+}
+
+#[test]
+fn test_diagonalizing_gates() {
+    let qubits = [0, 1, 2];
+    let ids = [1, 2, 3];
+    let basis_change =
+        diagonalizing_gates(&qubits, &ids)
+            .iter()
+            .fold(matrix::identity(8), |acc, g| {
+                let m = matrix::embed(&g.try_matrix().unwrap(), &g.get_qubits(), &qubits);
+                matrix::matmul(&m, &acc)
+            });
+    let rotated = matrix::matmul(
+        &matrix::matmul(&basis_change, &matrix::pauli_string(&ids)),
+        &matrix::dagger(&basis_change),
+    );
+    let expected = matrix::pauli_string(&[3, 3, 3]);
+    for (a, b) in rotated.iter().flatten().zip(expected.iter().flatten()) {
+        assert!((a - b).norm() < 1e-12);
+    }
+    assert!(diagonalizing_gates(&[4], &[0]).is_empty());
+}
+
+#[test]
+fn test_is_clifford_matrix() {
+    let clifford = [
+        QuantumGate::H(0),
+        QuantumGate::S(0),
+        QuantumGate::CNOT(0, 1),
+    ];
+    for gate in clifford.iter() {
+        assert!(is_clifford_matrix(&gate.try_matrix().unwrap(), 1e-9));
+    }
+    let non_clifford = [QuantumGate::T(0), QuantumGate::TOFFOLI(0, 1, 2)];
+    for gate in non_clifford.iter() {
+        assert!(!is_clifford_matrix(&gate.try_matrix().unwrap(), 1e-9));
+    }
+    assert!(QuantumGate::U3(0, FRAC_PI_2, 0.0, std::f64::consts::PI).is_clifford());
+    assert!(!QuantumGate::U1(0, 0.3).is_clifford());
+}
+
+#[test]
+fn test_pauli_decomposition() {
+    let y_z = matrix::pauli_string(&[2, 3]);
+    let (ids, coef) = pauli_decomposition(&y_z, 1e-12).unwrap();
+    assert_eq!(ids, vec![2, 3]);
+    assert!((coef - Complex64::new(1.0, 0.0)).norm() < 1e-12);
+    let h = QuantumGate::H(0).try_matrix().unwrap();
+    assert!(pauli_decomposition(&h, 1e-12).is_none());
+}
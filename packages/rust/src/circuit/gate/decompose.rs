@@ -0,0 +1,156 @@
+use crate::circuit::gate::QuantumGate;
+use pyo3::prelude::*;
+
+impl QuantumGate<f64> {
+    /// Returns gates acting on at most two qubits that implement the same unitary,
+    /// applied in order. TOFFOLI, the controlled rotations and Z with any number of
+    /// controls are decomposed; other gates are returned as is.
+    pub fn decompose(&self) -> Vec<Self> {
+        match self {
+            // Nielsen & Chuang, Figure 4.9.
+            Self::TOFFOLI(c1, c2, t) => {
+                let (c1, c2, t) = (*c1, *c2, *t);
+                vec![
+                    Self::H(t),
+                    Self::CNOT(c2, t),
+                    Self::Tdag(t),
+                    Self::CNOT(c1, t),
+                    Self::T(t),
+                    Self::CNOT(c2, t),
+                    Self::Tdag(t),
+                    Self::CNOT(c1, t),
+                    Self::T(c2),
+                    Self::T(t),
+                    Self::H(t),
+                    Self::CNOT(c1, c2),
+                    Self::T(c1),
+                    Self::Tdag(c2),
+                    Self::CNOT(c1, c2),
+                ]
+            }
+            // CRZ(theta) = RZ(theta/2) CNOT RZ(-theta/2) CNOT on the target, since
+            // X RZ(a) X = RZ(-a). The same holds for RY, and RX = H RZ H.
+            Self::CRX(c, t, p) | Self::CRY(c, t, p) | Self::CRZ(c, t, p) => {
+                let (c, t, half) = (*c, *t, p / 2.0);
+                let rotation = |a| match self {
+                    Self::CRY(_, _, _) => Self::RY(t, a),
+                    _ => Self::RZ(t, a),
+                };
+                let body = vec![
+                    rotation(half),
+                    Self::CNOT(c, t),
+                    rotation(-half),
+                    Self::CNOT(c, t),
+                ];
+                match self {
+                    Self::CRX(_, _, _) => [vec![Self::H(t)], body, vec![Self::H(t)]].concat(),
+                    _ => body,
+                }
+            }
+            Self::Other(prop)
+                if prop.name == "Z"
+                    && prop.target_indices.len() == 1
+                    && prop.control_indices.len() >= 2
+                    && prop.params.is_empty() =>
+            {
+                multi_controlled_z(&[&prop.control_indices[..], &prop.target_indices[..]].concat())
+            }
+            _ => vec![self.clone()],
+        }
+    }
+}
+
+/// Z controlled by all but one of `qubits`, which flips the sign of the state with
+/// every qubit 1. The AND of n bits is the sum of `(-1)^(|S|+1) parity(S) / 2^(n-1)`
+/// over the nonempty subsets S, so each term is a U1 on the last qubit of S between
+/// CNOTs that compute the parity of S on it.
+fn multi_controlled_z(qubits: &[usize]) -> Vec<QuantumGate> {
+    let unit = std::f64::consts::PI / 2f64.powi(qubits.len() as i32 - 1);
+    let mut gates = vec![];
+    for subset in 1..1usize << qubits.len() {
+        let members: Vec<usize> = (0..qubits.len())
+            .filter(|i| subset >> i & 1 == 1)
+            .map(|i| qubits[i])
+            .collect();
+        let (last, rest) = members.split_last().unwrap();
+        let ladder: Vec<QuantumGate> = rest.iter().map(|q| QuantumGate::CNOT(*q, *last)).collect();
+        let sign = if members.len() % 2 == 1 { 1.0 } else { -1.0 };
+        gates.extend(ladder.iter().cloned());
+        gates.push(QuantumGate::U1(*last, sign * unit));
+        gates.extend(ladder.into_iter().rev());
+    }
+    gates
+}
+
+/// The inverses of `gates` in reverse order, which undo the sequence.
+#[pyfunction]
+#[pyo3(text_signature = "(gates: Sequence[QuantumGate])")]
+pub fn inverse_sequence(gates: Vec<QuantumGate>) -> PyResult<Vec<QuantumGate>> {
+    gates.iter().rev().map(QuantumGate::inverse).collect()
+}
+
+#[test]
+fn test_decompose_toffoli() {
+    use crate::circuit::gate::matrix;
+    let gate = QuantumGate::TOFFOLI(2, 0, 1);
+    let qubits = [0, 1, 2];
+    let product = gate.decompose().iter().fold(matrix::identity(8), |acc, g| {
+        let m = matrix::embed(&g.try_matrix().unwrap(), &g.get_qubits(), &qubits);
+        matrix::matmul(&m, &acc)
+    });
+    let expected = matrix::embed(&gate.try_matrix().unwrap(), &gate.get_qubits(), &qubits);
+    for (a, b) in product.iter().flatten().zip(expected.iter().flatten()) {
+        assert!((a - b).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn test_decompose_multi_controlled_z() {
+    use crate::circuit::gate::{matrix, GenericGateProperty};
+    for (controls, target) in [(vec![0, 1], 2), (vec![3, 0, 2], 1)] {
+        let gate = QuantumGate::Other(Box::new(GenericGateProperty {
+            name: "Z".to_owned(),
+            target_indices: vec![target],
+            control_indices: controls.clone(),
+            classical_indices: vec![],
+            params: vec![],
+            pauli_ids: vec![],
+            unitary_matrix: None,
+        }));
+        let qubits: Vec<usize> = (0..=controls.len()).collect();
+        let dim = 1 << qubits.len();
+        let product = gate
+            .decompose()
+            .iter()
+            .fold(matrix::identity(dim), |acc, g| {
+                assert!(g.get_qubits().len() <= 2);
+                let m = matrix::embed(&g.try_matrix().unwrap(), &g.get_qubits(), &qubits);
+                matrix::matmul(&m, &acc)
+            });
+        let mut expected = matrix::identity(dim);
+        expected[dim - 1][dim - 1] = -expected[dim - 1][dim - 1];
+        for (a, b) in product.iter().flatten().zip(expected.iter().flatten()) {
+            assert!((a - b).norm() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_decompose_controlled_rotations() {
+    use crate::circuit::gate::matrix;
+    let qubits = [0, 1];
+    for gate in [
+        QuantumGate::CRX(1, 0, 0.7),
+        QuantumGate::CRY(0, 1, -1.3),
+        QuantumGate::CRZ(1, 0, 2.1),
+    ] {
+        let product = gate.decompose().iter().fold(matrix::identity(4), |acc, g| {
+            let m = matrix::embed(&g.try_matrix().unwrap(), &g.get_qubits(), &qubits);
+            matrix::matmul(&m, &acc)
+        });
+        let expected = matrix::embed(&gate.try_matrix().unwrap(), &gate.get_qubits(), &qubits);
+        for (a, b) in product.iter().flatten().zip(expected.iter().flatten()) {
+            assert!((a - b).norm() < 1e-12);
+        }
+    }
+}
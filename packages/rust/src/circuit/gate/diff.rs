@@ -0,0 +1,95 @@
+use crate::circuit::gate::{GenericGateProperty, QuantumGate};
+use pyo3::prelude::*;
+
+impl GenericGateProperty {
+    /// One line per field that differs from `other`, in field order. Parameters
+    /// are compared one by one and report the delta when both sides have them.
+    pub fn diff(&self, other: &Self) -> Vec<String> {
+        let mut changes = vec![];
+        if self.name != other.name {
+            changes.push(format!("name: {} -> {}", self.name, other.name));
+        }
+        let indices = [
+            (
+                "target_indices",
+                &self.target_indices,
+                &other.target_indices,
+            ),
+            (
+                "control_indices",
+                &self.control_indices,
+                &other.control_indices,
+            ),
+            (
+                "classical_indices",
+                &self.classical_indices,
+                &other.classical_indices,
+            ),
+        ];
+        for (field, before, after) in indices {
+            if before != after {
+                changes.push(format!("{}: {:?} -> {:?}", field, before, after));
+            }
+        }
+        if self.pauli_ids != other.pauli_ids {
+            changes.push(format!(
+                "pauli_ids: {:?} -> {:?}",
+                self.pauli_ids, other.pauli_ids
+            ));
+        }
+        for i in 0..self.params.len().max(other.params.len()) {
+            match (self.params.get(i), other.params.get(i)) {
+                (Some(a), Some(b)) if a != b => {
+                    changes.push(format!("params[{}]: {} -> {} ({:+})", i, a, b, b - a))
+                }
+                (Some(a), None) => changes.push(format!("params[{}]: {} -> removed", i, a)),
+                (None, Some(b)) => changes.push(format!("params[{}]: added -> {}", i, b)),
+                _ => {}
+            }
+        }
+        if self.unitary_matrix != other.unitary_matrix {
+            changes.push("unitary_matrix: changed".to_owned());
+        }
+        changes
+    }
+}
+
+impl QuantumGate<f64> {
+    /// Describes how `other` differs from `self`, one field per line, or `None`
+    /// when no field differs.
+    pub fn diff(&self, other: &Self) -> Option<String> {
+        let changes = self
+            .clone()
+            .into_property()
+            .diff(&other.clone().into_property());
+        (!changes.is_empty()).then(|| changes.join("\n"))
+    }
+}
+
+/// Describes what changed from `before` to `after`: the name, each index field
+/// and the delta of each parameter, one per line. Returns `None` when the gates
+/// are equal; durations are not compared.
+#[pyfunction]
+#[pyo3(text_signature = "(before: QuantumGate, after: QuantumGate)")]
+pub fn gate_diff(before: QuantumGate, after: QuantumGate) -> Option<String> {
+    before.diff(&after)
+}
+
+#[test]
+fn test_diff() {
+    assert_eq!(QuantumGate::RX(0, 0.5).diff(&QuantumGate::RX(0, 0.5)), None);
+    assert_eq!(
+        QuantumGate::RX(0, 0.5).diff(&QuantumGate::RY(1, 0.75)),
+        Some(
+            "name: RX -> RY\ntarget_indices: [0] -> [1]\nparams[0]: 0.5 -> 0.75 (+0.25)".to_owned()
+        )
+    );
+    assert_eq!(
+        QuantumGate::U1(0, 0.5).diff(&QuantumGate::U2(0, 0.5, 1.0)),
+        Some("name: U1 -> U2\nparams[1]: added -> 1".to_owned())
+    );
+    assert_eq!(
+        QuantumGate::CNOT(0, 1).diff(&QuantumGate::CNOT(2, 1)),
+        Some("control_indices: [0] -> [2]".to_owned())
+    );
+}
@@ -0,0 +1,21 @@
+// The exception macros of pyo3 0.22 check a `gil-refs` feature this crate does not declare.
+#![allow(unexpected_cfgs)]
+
+pyo3::create_exception!(
+    quri_parts.rust.circuit.gate,
+    UnknownGateError,
+    pyo3::exceptions::PyValueError,
+    "The gate name and fields do not form a gate known to the crate."
+);
+pyo3::create_exception!(
+    quri_parts.rust.circuit.gate,
+    InvalidUnitaryError,
+    pyo3::exceptions::PyValueError,
+    "The unitary matrix has the wrong shape or is not unitary."
+);
+pyo3::create_exception!(
+    quri_parts.rust.circuit.gate,
+    IndexConflictError,
+    pyo3::exceptions::PyValueError,
+    "A qubit is used more than once by the same gate."
+);
@@ -0,0 +1,379 @@
+use crate::circuit::gate::GenericGateProperty;
+use num_complex::Complex64;
+use pyo3::prelude::*;
+
+/// Errors are kept as plain strings until they reach the Python boundary.
+type JsonResult<T> = Result<T, String>;
+
+fn json_error(reason: impl std::fmt::Display) -> String {
+    reason.to_string()
+}
+
+fn write_str(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Writes `v` with the shortest representation that parses back to the same value.
+fn write_real(out: &mut String, v: f64) -> JsonResult<()> {
+    if !v.is_finite() {
+        return Err(json_error(format!("{} cannot be represented in JSON", v)));
+    }
+    out.push_str(&format!("{:?}", v));
+    Ok(())
+}
+
+fn write_list<T>(
+    out: &mut String,
+    items: &[T],
+    mut f: impl FnMut(&mut String, &T) -> JsonResult<()>,
+) -> JsonResult<()> {
+    out.push('[');
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        f(out, item)?;
+    }
+    out.push(']');
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_ws(&mut self) {
+        let rest = &self.src[self.pos..];
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.as_bytes().get(self.pos).copied()
+    }
+
+    fn expect(&mut self, c: u8) -> JsonResult<()> {
+        self.skip_ws();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(json_error(format!(
+                "expected '{}' at position {}",
+                c as char, self.pos
+            )))
+        }
+    }
+
+    fn parse_value(&mut self) -> JsonResult<Value> {
+        self.skip_ws();
+        match self.peek() {
+            Some(b'n') => self.parse_keyword("null", Value::Null),
+            Some(b't') => self.parse_keyword("true", Value::Bool(true)),
+            Some(b'f') => self.parse_keyword("false", Value::Bool(false)),
+            Some(b'"') => Ok(Value::String(self.parse_string()?)),
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = vec![];
+                self.skip_ws();
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                loop {
+                    items.push(self.parse_value()?);
+                    self.skip_ws();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        _ => break,
+                    }
+                }
+                self.expect(b']')?;
+                Ok(Value::Array(items))
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut entries = vec![];
+                self.skip_ws();
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    return Ok(Value::Object(entries));
+                }
+                loop {
+                    self.skip_ws();
+                    let key = self.parse_string()?;
+                    self.expect(b':')?;
+                    entries.push((key, self.parse_value()?));
+                    self.skip_ws();
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        _ => break,
+                    }
+                }
+                self.expect(b'}')?;
+                Ok(Value::Object(entries))
+            }
+            Some(_) => self.parse_number(),
+            None => Err(json_error("unexpected end of input")),
+        }
+    }
+
+    fn parse_keyword(&mut self, keyword: &str, value: Value) -> JsonResult<Value> {
+        if self.src[self.pos..].starts_with(keyword) {
+            self.pos += keyword.len();
+            Ok(value)
+        } else {
+            Err(json_error(format!(
+                "unexpected token at position {}",
+                self.pos
+            )))
+        }
+    }
+
+    fn parse_number(&mut self) -> JsonResult<Value> {
+        let rest = &self.src[self.pos..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')))
+            .unwrap_or(rest.len());
+        let v = rest[..len]
+            .parse::<f64>()
+            .ok()
+            .filter(|_| len > 0)
+            .ok_or_else(|| json_error(format!("unexpected token at position {}", self.pos)))?;
+        self.pos += len;
+        Ok(Value::Number(v))
+    }
+
+    fn parse_string(&mut self) -> JsonResult<String> {
+        self.expect(b'"')?;
+        let mut out = String::new();
+        let mut chars = self.src[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('"') => out.push('"'),
+                    Some('\\') => out.push('\\'),
+                    Some('/') => out.push('/'),
+                    Some('b') => out.push('\u{8}'),
+                    Some('f') => out.push('\u{c}'),
+                    Some('n') => out.push('\n'),
+                    Some('r') => out.push('\r'),
+                    Some('t') => out.push('\t'),
+                    Some('u') => {
+                        let mut code = 0;
+                        for _ in 0..4 {
+                            let d = chars.next().and_then(|(_, c)| c.to_digit(16));
+                            code = code * 16 + d.ok_or_else(|| json_error("invalid escape"))?;
+                        }
+                        out.push(char::from_u32(code).ok_or_else(|| json_error("invalid escape"))?);
+                    }
+                    _ => return Err(json_error("invalid escape")),
+                },
+                c => out.push(c),
+            }
+        }
+        Err(json_error("unterminated string"))
+    }
+}
+
+fn as_array(value: Value, field: &str) -> JsonResult<Vec<Value>> {
+    match value {
+        Value::Array(items) => Ok(items),
+        _ => Err(json_error(format!("'{}' must be an array", field))),
+    }
+}
+
+fn as_real(value: Value, field: &str) -> JsonResult<f64> {
+    match value {
+        Value::Number(v) => Ok(v),
+        _ => Err(json_error(format!("'{}' must contain numbers", field))),
+    }
+}
+
+fn as_index<T: TryFrom<u64>>(value: Value, field: &str) -> JsonResult<T> {
+    match value {
+        Value::Number(v) if v >= 0.0 && v.fract() == 0.0 => T::try_from(v as u64).ok(),
+        _ => None,
+    }
+    .ok_or_else(|| json_error(format!("'{}' must contain non-negative integers", field)))
+}
+
+fn as_list<T>(
+    value: Value,
+    field: &str,
+    f: impl Fn(Value, &str) -> JsonResult<T>,
+) -> JsonResult<Vec<T>> {
+    as_array(value, field)?
+        .into_iter()
+        .map(|v| f(v, field))
+        .collect()
+}
+
+impl GenericGateProperty {
+    /// Serializes the property as a JSON object. Complex matrix elements are
+    /// written as `[re, im]` pairs and a missing matrix as `null`.
+    pub fn to_json(&self) -> PyResult<String> {
+        self.write_json().map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("Cannot serialize gate: {}", e))
+        })
+    }
+
+    /// Parses the JSON object produced by `to_json()`. Only `name` and
+    /// `target_indices` are required.
+    pub fn from_json(s: &str) -> PyResult<Self> {
+        Self::read_json(s).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("Invalid gate JSON: {}", e))
+        })
+    }
+
+    fn write_json(&self) -> JsonResult<String> {
+        let mut out = String::from("{\"name\": ");
+        write_str(&mut out, &self.name);
+        for (key, indices) in [
+            ("target_indices", &self.target_indices),
+            ("control_indices", &self.control_indices),
+            ("classical_indices", &self.classical_indices),
+        ] {
+            out.push_str(&format!(", \"{}\": ", key));
+            write_list(&mut out, indices, |out, i| {
+                out.push_str(&i.to_string());
+                Ok(())
+            })?;
+        }
+        out.push_str(", \"params\": ");
+        write_list(&mut out, &self.params, |out, p| write_real(out, *p))?;
+        out.push_str(", \"pauli_ids\": ");
+        write_list(&mut out, &self.pauli_ids, |out, p| {
+            out.push_str(&p.to_string());
+            Ok(())
+        })?;
+        out.push_str(", \"unitary_matrix\": ");
+        match &self.unitary_matrix {
+            Some(mat) => write_list(&mut out, mat, |out, row| {
+                write_list(out, row, |out, c| {
+                    out.push('[');
+                    write_real(out, c.re)?;
+                    out.push_str(", ");
+                    write_real(out, c.im)?;
+                    out.push(']');
+                    Ok(())
+                })
+            })?,
+            None => out.push_str("null"),
+        }
+        out.push('}');
+        Ok(out)
+    }
+
+    fn read_json(s: &str) -> JsonResult<Self> {
+        let mut parser = Parser { src: s, pos: 0 };
+        let value = parser.parse_value()?;
+        parser.skip_ws();
+        if parser.pos != s.len() {
+            return Err(json_error(format!(
+                "trailing characters at position {}",
+                parser.pos
+            )));
+        }
+        let Value::Object(entries) = value else {
+            return Err(json_error("a gate must be a JSON object"));
+        };
+        let mut prop = GenericGateProperty {
+            name: String::new(),
+            target_indices: vec![],
+            control_indices: vec![],
+            classical_indices: vec![],
+            params: vec![],
+            pauli_ids: vec![],
+            unitary_matrix: None,
+        };
+        let (mut has_name, mut has_targets) = (false, false);
+        for (key, value) in entries {
+            match key.as_str() {
+                "name" => match value {
+                    Value::String(name) => {
+                        prop.name = name;
+                        has_name = true;
+                    }
+                    _ => return Err(json_error("'name' must be a string")),
+                },
+                "target_indices" => {
+                    prop.target_indices = as_list(value, &key, as_index)?;
+                    has_targets = true;
+                }
+                "control_indices" => prop.control_indices = as_list(value, &key, as_index)?,
+                "classical_indices" => prop.classical_indices = as_list(value, &key, as_index)?,
+                "params" => prop.params = as_list(value, &key, as_real)?,
+                "pauli_ids" => prop.pauli_ids = as_list(value, &key, as_index)?,
+                "unitary_matrix" => {
+                    prop.unitary_matrix = match value {
+                        Value::Null => None,
+                        value => Some(as_list(value, &key, |row, key| {
+                            as_list(row, key, |c, key| {
+                                let c = as_list(c, key, as_real)?;
+                                match c[..] {
+                                    [re, im] => Ok(Complex64::new(re, im)),
+                                    _ => Err(json_error("matrix elements must be [re, im] pairs")),
+                                }
+                            })
+                        })?),
+                    }
+                }
+                _ => return Err(json_error(format!("unknown field '{}'", key))),
+            }
+        }
+        if !has_name || !has_targets {
+            return Err(json_error("'name' and 'target_indices' are required"));
+        }
+        Ok(prop)
+    }
+}
+
+#[test]
+fn test_json_roundtrip() {
+    let prop = GenericGateProperty {
+        name: "Custom \"gate\"\n".to_owned(),
+        target_indices: vec![0, 3],
+        control_indices: vec![1],
+        classical_indices: vec![],
+        params: vec![0.1, -1e-300, 1.0 / 3.0],
+        pauli_ids: vec![1, 3],
+        unitary_matrix: Some(vec![
+            vec![Complex64::new(0.5, -0.25), Complex64::new(0.0, 1.0)],
+            vec![Complex64::new(1e20, 0.0), Complex64::new(-0.0, 2.5)],
+        ]),
+    };
+    let parsed = GenericGateProperty::read_json(&prop.write_json().unwrap()).unwrap();
+    assert_eq!(parsed.name, prop.name);
+    assert_eq!(parsed.params, prop.params);
+    assert_eq!(parsed.pauli_ids, prop.pauli_ids);
+    assert_eq!(parsed.target_indices, prop.target_indices);
+    assert_eq!(parsed.unitary_matrix, prop.unitary_matrix);
+}
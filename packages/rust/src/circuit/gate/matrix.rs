@@ -0,0 +1,1157 @@
+use crate::circuit::gate::{GenericGateProperty, QuantumGate};
+use num_complex::Complex64;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2, FRAC_PI_4, TAU};
+
+/// Dense matrix on the qubits returned by `QuantumGate::get_qubits()`.
+///
+/// The i-th qubit of that list corresponds to the i-th bit (from the least
+/// significant one) of the row and column indices, which is the same
+/// convention as the `unitary_matrix` of UnitaryMatrix gates.
+pub type Matrix = Vec<Vec<Complex64>>;
+
+const ZERO: Complex64 = Complex64::new(0.0, 0.0);
+const ONE: Complex64 = Complex64::new(1.0, 0.0);
+const I: Complex64 = Complex64::new(0.0, 1.0);
+
+pub fn identity(dim: usize) -> Matrix {
+    (0..dim)
+        .map(|i| (0..dim).map(|j| if i == j { ONE } else { ZERO }).collect())
+        .collect()
+}
+
+pub fn dagger(mat: &Matrix) -> Matrix {
+    (0..mat.len())
+        .map(|i| mat.iter().map(|row| row[i].conj()).collect())
+        .collect()
+}
+
+/// Whether `mat` is square and `mat mat^dag` is the identity within `atol`.
+pub fn is_unitary(mat: &Matrix, atol: f64) -> bool {
+    let dim = mat.len();
+    mat.iter().all(|row| row.len() == dim)
+        && (0..dim).all(|i| {
+            (0..dim).all(|j| {
+                let v: Complex64 = (0..dim).map(|k| mat[i][k] * mat[j][k].conj()).sum();
+                (v - if i == j { ONE } else { ZERO }).norm() < atol
+            })
+        })
+}
+
+pub fn matmul(lhs: &Matrix, rhs: &Matrix) -> Matrix {
+    (0..lhs.len())
+        .map(|i| {
+            (0..rhs[0].len())
+                .map(|j| (0..rhs.len()).map(|k| lhs[i][k] * rhs[k][j]).sum())
+                .collect()
+        })
+        .collect()
+}
+
+/// `mat` with the bit order of its basis reversed, so that the first qubit is
+/// the most significant bit instead of the least significant one.
+pub fn reverse_bit_order(mat: &Matrix) -> Matrix {
+    let n = mat.len().trailing_zeros() as usize;
+    let qubits: Vec<usize> = (0..n).collect();
+    let reversed: Vec<usize> = (0..n).rev().collect();
+    embed(mat, &qubits, &reversed)
+}
+
+/// `mat` raised to the `n`-th power by repeated squaring.
+pub fn power(mat: &Matrix, mut n: u64) -> Matrix {
+    let mut result = identity(mat.len());
+    let mut base = mat.clone();
+    while n > 0 {
+        if n & 1 == 1 {
+            result = matmul(&result, &base);
+        }
+        n >>= 1;
+        if n > 0 {
+            base = matmul(&base, &base);
+        }
+    }
+    result
+}
+
+/// Embeds `mat` acting on `gate_qubits` into the space of `qubits`, which must
+/// contain all of `gate_qubits`.
+pub fn embed(mat: &Matrix, gate_qubits: &[usize], qubits: &[usize]) -> Matrix {
+    let pos: Vec<usize> = gate_qubits
+        .iter()
+        .map(|q| qubits.iter().position(|p| p == q).unwrap())
+        .collect();
+    let rest = !pos.iter().fold(0usize, |acc, p| acc | 1 << p);
+    let sub = |x: usize| {
+        pos.iter()
+            .enumerate()
+            .fold(0, |acc, (k, p)| acc | ((x >> p) & 1) << k)
+    };
+    let dim = 1 << qubits.len();
+    (0..dim)
+        .map(|i| {
+            (0..dim)
+                .map(|j| {
+                    if i & rest == j & rest {
+                        mat[sub(i)][sub(j)]
+                    } else {
+                        ZERO
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Whether `rhs = e^{i phi} lhs` for some phase phi, within `atol`. The phase is
+/// taken from the first entry of `lhs` that is not zero.
+pub fn equal_up_to_phase(lhs: &Matrix, rhs: &Matrix, atol: f64) -> bool {
+    if lhs.len() != rhs.len() || lhs.iter().zip(rhs).any(|(l, r)| l.len() != r.len()) {
+        return false;
+    }
+    let Some((l, r)) = lhs
+        .iter()
+        .flatten()
+        .zip(rhs.iter().flatten())
+        .find(|(l, _)| l.norm() > atol)
+    else {
+        return rhs.iter().flatten().all(|r| r.norm() <= atol);
+    };
+    let phase = r / l;
+    let phase = phase / phase.norm();
+    lhs.iter()
+        .flatten()
+        .zip(rhs.iter().flatten())
+        .all(|(l, r)| (l * phase - r).norm() <= atol)
+}
+
+/// Whether `lhs` on `lhs_qubits` and `rhs` on `rhs_qubits` commute within `atol`.
+pub fn commutes(
+    lhs: &Matrix,
+    lhs_qubits: &[usize],
+    rhs: &Matrix,
+    rhs_qubits: &[usize],
+    atol: f64,
+) -> bool {
+    let mut qubits = lhs_qubits.to_vec();
+    qubits.extend(rhs_qubits.iter().filter(|q| !lhs_qubits.contains(q)));
+    let lhs = embed(lhs, lhs_qubits, &qubits);
+    let rhs = embed(rhs, rhs_qubits, &qubits);
+    let (ab, ba) = (matmul(&lhs, &rhs), matmul(&rhs, &lhs));
+    ab.iter()
+        .flatten()
+        .zip(ba.iter().flatten())
+        .all(|(x, y)| (x - y).norm() <= atol)
+}
+
+/// |Tr(lhs^dag rhs)|^2 / d^2 for matrices of dimension d, clamped to at most 1
+/// against rounding.
+pub fn process_fidelity(lhs: &Matrix, rhs: &Matrix) -> f64 {
+    let overlap: Complex64 = lhs
+        .iter()
+        .flatten()
+        .zip(rhs.iter().flatten())
+        .map(|(a, b)| a.conj() * b)
+        .sum();
+    let dim = lhs.len() as f64;
+    (overlap.norm_sqr() / (dim * dim)).min(1.0)
+}
+
+/// (d F + 1) / (d + 1), where F is the `process_fidelity` of matrices of dimension d.
+pub fn average_gate_fidelity(lhs: &Matrix, rhs: &Matrix) -> f64 {
+    let dim = lhs.len() as f64;
+    (dim * process_fidelity(lhs, rhs) + 1.0) / (dim + 1.0)
+}
+
+pub fn trace(mat: &Matrix) -> Complex64 {
+    mat.iter().enumerate().map(|(i, row)| row[i]).sum()
+}
+
+/// Determinant by Gaussian elimination with partial pivoting.
+pub fn determinant(mat: &Matrix) -> Complex64 {
+    let mut a = mat.clone();
+    let dim = a.len();
+    let mut det = ONE;
+    for k in 0..dim {
+        let pivot = (k..dim)
+            .max_by(|i, j| a[*i][k].norm().total_cmp(&a[*j][k].norm()))
+            .unwrap();
+        if a[pivot][k] == ZERO {
+            return ZERO;
+        }
+        if pivot != k {
+            a.swap(pivot, k);
+            det = -det;
+        }
+        det *= a[k][k];
+        let (top, bottom) = a.split_at_mut(k + 1);
+        let pivot_row = &top[k];
+        for row in bottom {
+            let factor = row[k] / pivot_row[k];
+            for (x, p) in row.iter_mut().zip(pivot_row).skip(k) {
+                *x -= factor * p;
+            }
+        }
+    }
+    det
+}
+
+/// Number of entries whose modulus exceeds `atol`.
+pub fn nnz(mat: &Matrix, atol: f64) -> usize {
+    mat.iter().flatten().filter(|c| c.norm() > atol).count()
+}
+
+/// Row indices, column indices and values of the entries whose modulus exceeds
+/// `atol`, in row-major order.
+pub fn to_coo(mat: &Matrix, atol: f64) -> (Vec<usize>, Vec<usize>, Vec<Complex64>) {
+    let mut coo = (vec![], vec![], vec![]);
+    for (i, row) in mat.iter().enumerate() {
+        for (j, c) in row.iter().enumerate().filter(|(_, c)| c.norm() > atol) {
+            coo.0.push(i);
+            coo.1.push(j);
+            coo.2.push(*c);
+        }
+    }
+    coo
+}
+
+/// Whether every row and column has exactly one nonzero entry and it has modulus 1,
+/// i.e. the matrix is a permutation matrix up to phases on the entries.
+pub fn is_permutation(mat: &Matrix, atol: f64) -> bool {
+    let dim = mat.len();
+    let mut column_used = vec![false; dim];
+    for row in mat {
+        let mut nonzero = row.iter().enumerate().filter(|(_, c)| c.norm() > atol);
+        let (Some((j, c)), None) = (nonzero.next(), nonzero.next()) else {
+            return false;
+        };
+        if (c.norm() - 1.0).abs() > atol || column_used[j] {
+            return false;
+        }
+        column_used[j] = true;
+    }
+    true
+}
+
+/// The output basis index of each input basis index if the matrix is a 0/1
+/// permutation matrix. Entries with a phase other than 1 are rejected, since the
+/// gate then does not act on basis states as a plain permutation.
+pub fn basis_permutation(mat: &Matrix, atol: f64) -> Option<Vec<usize>> {
+    if !is_permutation(mat, atol) {
+        return None;
+    }
+    (0..mat.len())
+        .map(|j| {
+            let i = (0..mat.len()).find(|i| mat[*i][j].norm() > atol)?;
+            ((mat[i][j] - ONE).norm() <= atol).then_some(i)
+        })
+        .collect()
+}
+
+/// Kronecker product `lhs ⊗ rhs`. The qubits of `rhs` occupy the lower bits.
+pub fn kron(lhs: &Matrix, rhs: &Matrix) -> Matrix {
+    let dr = rhs.len();
+    let dim = lhs.len() * dr;
+    (0..dim)
+        .map(|i| {
+            (0..dim)
+                .map(|j| lhs[i / dr][j / dr] * rhs[i % dr][j % dr])
+                .collect()
+        })
+        .collect()
+}
+
+/// Extends `base` with `control_count` control qubits placed on the lower bits.
+pub fn controlled(control_count: usize, base: &Matrix) -> Matrix {
+    let cdim = 1 << control_count;
+    let dim = cdim * base.len();
+    (0..dim)
+        .map(|i| {
+            (0..dim)
+                .map(|j| {
+                    if i % cdim != j % cdim {
+                        ZERO
+                    } else if i % cdim == cdim - 1 {
+                        base[i / cdim][j / cdim]
+                    } else if i == j {
+                        ONE
+                    } else {
+                        ZERO
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn pauli(pauli_id: u8) -> Matrix {
+    match pauli_id {
+        1 => vec![vec![ZERO, ONE], vec![ONE, ZERO]],
+        2 => vec![vec![ZERO, -I], vec![I, ZERO]],
+        3 => vec![vec![ONE, ZERO], vec![ZERO, -ONE]],
+        _ => identity(2),
+    }
+}
+
+/// Tensor product of the Pauli operators, the first id acting on the lowest bit.
+pub fn pauli_string(pauli_ids: &[u8]) -> Matrix {
+    pauli_ids
+        .iter()
+        .fold(identity(1), |acc, p| kron(&pauli(*p), &acc))
+}
+
+/// exp(-i angle/2 P) for the Pauli string P.
+pub fn pauli_rotation(pauli_ids: &[u8], angle: f64) -> Matrix {
+    let p = pauli_string(pauli_ids);
+    let (c, s) = ((angle / 2.0).cos(), (angle / 2.0).sin());
+    (0..p.len())
+        .map(|i| {
+            (0..p.len())
+                .map(|j| if i == j { c * ONE } else { ZERO } - I * s * p[i][j])
+                .collect()
+        })
+        .collect()
+}
+
+fn scaled(mat: &Matrix, factor: f64) -> Matrix {
+    mat.iter()
+        .map(|row| row.iter().map(|c| c * factor).collect())
+        .collect()
+}
+
+fn u3(theta: f64, phi: f64, lmd: f64) -> Matrix {
+    let (c, s) = ((theta / 2.0).cos(), (theta / 2.0).sin());
+    vec![
+        vec![c * ONE, -Complex64::from_polar(s, lmd)],
+        vec![
+            Complex64::from_polar(s, phi),
+            Complex64::from_polar(c, phi + lmd),
+        ],
+    ]
+}
+
+fn diag(entries: &[Complex64]) -> Matrix {
+    (0..entries.len())
+        .map(|i| {
+            (0..entries.len())
+                .map(|j| if i == j { entries[i] } else { ZERO })
+                .collect()
+        })
+        .collect()
+}
+
+impl QuantumGate<f64> {
+    /// Named gates are unitary by definition, so only the stored matrices are checked.
+    pub fn is_unitary(&self, atol: f64) -> bool {
+        match self {
+            Self::UnitaryMatrix(_, mat) => is_unitary(mat, atol),
+            Self::Other(o) => o
+                .unitary_matrix
+                .as_ref()
+                .is_some_and(|mat| is_unitary(mat, atol)),
+            Self::Measurement(_, _) => false,
+            _ => true,
+        }
+    }
+
+    /// Gates on disjoint qubits always commute. Otherwise the matrices are compared
+    /// on the union of their qubits.
+    pub fn commutes_with(&self, other: &Self, atol: f64) -> PyResult<bool> {
+        let (lhs_qubits, rhs_qubits) = (self.get_qubits(), other.get_qubits());
+        if lhs_qubits.iter().all(|q| !rhs_qubits.contains(q)) {
+            return Ok(true);
+        }
+        Ok(commutes(
+            &self.matrix()?,
+            &lhs_qubits,
+            &other.matrix()?,
+            &rhs_qubits,
+            atol,
+        ))
+    }
+
+    /// Named gates with close parameters are equivalent. Otherwise the matrices are
+    /// compared on the same qubit order up to a global phase, so that e.g.
+    /// `U1(0, 10)` matches `U1(0, 10 - 4pi)` and `X(0)` matches `Pauli([0], [1])`.
+    /// Named gates without a matrix are only equivalent by their parameters.
+    pub fn equiv_up_to_global_phase(&self, other: &Self, atol: f64) -> PyResult<bool> {
+        let is_matrix_gate = |g: &Self| matches!(g, Self::UnitaryMatrix(_, _) | Self::Other(_));
+        if !is_matrix_gate(self) && !is_matrix_gate(other) {
+            if self
+                .clone()
+                .into_property()
+                .isclose(&other.clone().into_property(), 0.0, atol)
+            {
+                return Ok(true);
+            }
+            if self.try_matrix().is_none() || other.try_matrix().is_none() {
+                return Ok(false);
+            }
+        }
+        let (lhs_qubits, rhs_qubits) = (self.get_qubits(), other.get_qubits());
+        if lhs_qubits.len() != rhs_qubits.len()
+            || lhs_qubits.iter().any(|q| !rhs_qubits.contains(q))
+        {
+            return Ok(false);
+        }
+        let rhs = embed(&other.matrix()?, &rhs_qubits, &lhs_qubits);
+        Ok(equal_up_to_phase(&self.matrix()?, &rhs, atol))
+    }
+
+    /// Whether applying the gate and then `other` yields the identity up to a global
+    /// phase. Rotations about the same axis are inverse when their angles sum to a
+    /// multiple of 2pi, named gates are compared with `inverse()`, and the rest are
+    /// checked by multiplying the matrices.
+    pub fn is_inverse_of(&self, other: &Self, atol: f64) -> bool {
+        let angle_sum = match (self, other) {
+            (Self::RX(q, a), Self::RX(r, b))
+            | (Self::RY(q, a), Self::RY(r, b))
+            | (Self::RZ(q, a), Self::RZ(r, b))
+                if q == r =>
+            {
+                Some(a + b)
+            }
+            (Self::PauliRotation(_, _, a), Self::PauliRotation(_, _, b))
+                if self
+                    .clone()
+                    .into_property()
+                    .structurally_equal(&other.clone().into_property()) =>
+            {
+                Some(a + b)
+            }
+            _ => None,
+        };
+        if let Some(sum) = angle_sum {
+            let turns = sum / TAU;
+            return (turns - turns.round()).abs() * TAU <= atol;
+        }
+        if let Ok(inverse) = self.inverse() {
+            if inverse.into_property() == other.clone().into_property() {
+                return true;
+            }
+        }
+        let (Some(lhs), Some(rhs)) = (self.try_matrix(), other.try_matrix()) else {
+            return false;
+        };
+        let (lhs_qubits, rhs_qubits) = (self.get_qubits(), other.get_qubits());
+        let mut qubits = lhs_qubits.clone();
+        qubits.extend(rhs_qubits.iter().filter(|q| !lhs_qubits.contains(q)));
+        let product = matmul(
+            &embed(&rhs, &rhs_qubits, &qubits),
+            &embed(&lhs, &lhs_qubits, &qubits),
+        );
+        equal_up_to_phase(&product, &identity(product.len()), atol)
+    }
+
+    /// Average gate fidelity (d F + 1) / (d + 1) between the matrices of the gates,
+    /// where F = |Tr(U^dag V)|^2 / d^2 is the process fidelity. The gates must act
+    /// on the same set of qubits.
+    pub fn process_fidelity(&self, other: &Self) -> PyResult<f64> {
+        let (lhs_qubits, rhs_qubits) = (self.get_qubits(), other.get_qubits());
+        if lhs_qubits.len() != rhs_qubits.len()
+            || lhs_qubits.iter().any(|q| !rhs_qubits.contains(q))
+        {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "Gates must act on the same qubits: {:?} and {:?}",
+                lhs_qubits, rhs_qubits
+            )));
+        }
+        let rhs = embed(&other.matrix()?, &rhs_qubits, &lhs_qubits);
+        Ok(average_gate_fidelity(&self.matrix()?, &rhs))
+    }
+
+    /// Whether the matrix of the gate is diagonal in the computational basis. Named
+    /// phase gates are recognized directly and the others are checked numerically.
+    pub fn is_diagonal(&self, atol: f64) -> bool {
+        match self {
+            Self::Identity(_)
+            | Self::Z(_)
+            | Self::S(_)
+            | Self::Sdag(_)
+            | Self::T(_)
+            | Self::Tdag(_)
+            | Self::RZ(_, _)
+            | Self::U1(_, _)
+            | Self::GlobalPhase(_, _)
+            | Self::CZ(_, _)
+            | Self::CRZ(_, _, _) => true,
+            Self::Pauli(_, ps) | Self::PauliRotation(_, ps, _) if ps.iter().all(|p| *p == 3) => {
+                true
+            }
+            _ => self.try_matrix().is_some_and(|mat| {
+                mat.iter().enumerate().all(|(i, row)| {
+                    row.iter()
+                        .enumerate()
+                        .all(|(j, c)| i == j || c.norm() <= atol)
+                })
+            }),
+        }
+    }
+
+    /// Whether the gate acts as the identity. Rotations are checked by angle modulo
+    /// 2pi, or 4pi when the global phase matters, and other gates by their matrix.
+    pub fn is_identity(&self, atol: f64, up_to_global_phase: bool) -> bool {
+        match self {
+            Self::Identity(_) => true,
+            Self::RX(_, a) | Self::RY(_, a) | Self::RZ(_, a) | Self::PauliRotation(_, _, a) => {
+                let period = if up_to_global_phase { TAU } else { 2.0 * TAU };
+                let r = a.rem_euclid(period);
+                r.min(period - r) <= atol
+            }
+            _ => self.try_matrix().is_some_and(|mat| {
+                let id = identity(mat.len());
+                if up_to_global_phase {
+                    equal_up_to_phase(&mat, &id, atol)
+                } else {
+                    mat.iter()
+                        .flatten()
+                        .zip(id.iter().flatten())
+                        .all(|(m, i)| (m - i).norm() <= atol)
+                }
+            }),
+        }
+    }
+
+    /// The same two-qubit UnitaryMatrix gate with its targets listed in the
+    /// opposite order and the matrix reindexed to match.
+    pub fn with_swapped_order(&self) -> PyResult<Self> {
+        match self {
+            Self::UnitaryMatrix(qs, mat) if qs.len() == 2 => {
+                let swapped = vec![qs[1], qs[0]];
+                Ok(Self::UnitaryMatrix(
+                    swapped.clone(),
+                    embed(mat, qs, &swapped),
+                ))
+            }
+            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "{} gate is not a two-qubit UnitaryMatrix gate.",
+                &self.clone().into_property().name
+            ))),
+        }
+    }
+
+    /// An equivalent UnitaryMatrix gate on `get_qubits()`, controls first, for
+    /// backends that only take matrices. UnitaryMatrix gates are returned as is.
+    pub fn to_matrix_gate(&self) -> PyResult<Self> {
+        match self {
+            Self::UnitaryMatrix(_, _) => Ok(self.clone()),
+            _ => Ok(Self::UnitaryMatrix(self.get_qubits(), self.matrix()?)),
+        }
+    }
+
+    /// A single gate equal to applying the gate `n` times. Rotations scale their
+    /// angle, self-inverse gates reduce `n` mod 2, and other gates become a
+    /// UnitaryMatrix gate with the `n`-th power of their matrix. A negative `n`
+    /// powers the inverse, and an even power of a self-inverse gate is the identity
+    /// on its qubits.
+    pub fn power(&self, n: i64) -> PyResult<Self> {
+        if n < 0 {
+            return self.inverse()?.power_unsigned(n.unsigned_abs());
+        }
+        self.power_unsigned(n as u64)
+    }
+
+    fn power_unsigned(&self, n: u64) -> PyResult<Self> {
+        let identity_on = |qubits: Vec<usize>| match qubits[..] {
+            [q] => Self::Identity(q),
+            _ => Self::Pauli(qubits.clone(), vec![0; qubits.len()]),
+        };
+        match self {
+            _ if n == 1 => Ok(self.clone()),
+            Self::RX(_, _)
+            | Self::RY(_, _)
+            | Self::RZ(_, _)
+            | Self::U1(_, _)
+            | Self::GlobalPhase(_, _)
+            | Self::CRX(_, _, _)
+            | Self::CRY(_, _, _)
+            | Self::CRZ(_, _, _)
+            | Self::PauliRotation(_, _, _) => self.scale_params(n as f64),
+            Self::Identity(_)
+            | Self::X(_)
+            | Self::Y(_)
+            | Self::Z(_)
+            | Self::H(_)
+            | Self::CNOT(_, _)
+            | Self::CZ(_, _)
+            | Self::SWAP(_, _)
+            | Self::ECR(_, _)
+            | Self::TOFFOLI(_, _, _)
+            | Self::Pauli(_, _) => Ok(if n % 2 == 1 {
+                self.clone()
+            } else {
+                identity_on(self.get_qubits())
+            }),
+            _ => {
+                let mat = self.matrix()?;
+                Ok(if n == 0 {
+                    identity_on(self.get_qubits())
+                } else {
+                    Self::UnitaryMatrix(self.get_qubits(), power(&mat, n))
+                })
+            }
+        }
+    }
+
+    pub fn trace(&self) -> PyResult<Complex64> {
+        Ok(trace(&self.matrix()?))
+    }
+
+    /// The determinant is 1 exactly for special unitary matrices.
+    pub fn determinant(&self) -> PyResult<Complex64> {
+        Ok(determinant(&self.matrix()?))
+    }
+
+    pub fn nnz(&self, atol: f64) -> PyResult<usize> {
+        Ok(nnz(&self.matrix()?, atol))
+    }
+
+    pub fn to_coo(&self, atol: f64) -> PyResult<(Vec<usize>, Vec<usize>, Vec<Complex64>)> {
+        Ok(to_coo(&self.matrix()?, atol))
+    }
+
+    pub fn basis_permutation(&self) -> PyResult<Vec<usize>> {
+        basis_permutation(&self.matrix()?, 1e-10).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "{} gate does not permute the computational basis.",
+                &self.clone().into_property().name
+            ))
+        })
+    }
+
+    pub fn is_permutation(&self, atol: f64) -> PyResult<bool> {
+        Ok(is_permutation(&self.matrix()?, atol))
+    }
+
+    /// The Hermitian H with `exp(-iH)` equal to the matrix, in the same qubit
+    /// order. Rotations give angle/2 times their Pauli string, controlled rotations
+    /// the same on the control's |1> subspace, U1(lmd) gives -lmd|1><1| and
+    /// GlobalPhase(p) gives -p I.
+    pub fn generator(&self) -> PyResult<Matrix> {
+        self.try_generator().ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "{} gate does not have a single generator form.",
+                &self.clone().into_property().name
+            ))
+        })
+    }
+
+    pub fn try_generator(&self) -> Option<Matrix> {
+        let rotation = |ps: &[u8], angle: f64| scaled(&pauli_string(ps), angle / 2.0);
+        let on_control = |h: Matrix| kron(&h, &diag(&[ZERO, ONE]));
+        match self {
+            Self::RX(_, a) => Some(rotation(&[1], *a)),
+            Self::RY(_, a) => Some(rotation(&[2], *a)),
+            Self::RZ(_, a) => Some(rotation(&[3], *a)),
+            Self::PauliRotation(_, ps, a) => Some(rotation(ps, *a)),
+            Self::CRX(_, _, a) => Some(on_control(rotation(&[1], *a))),
+            Self::CRY(_, _, a) => Some(on_control(rotation(&[2], *a))),
+            Self::CRZ(_, _, a) => Some(on_control(rotation(&[3], *a))),
+            Self::U1(_, lmd) => Some(diag(&[ZERO, -lmd * ONE])),
+            Self::GlobalPhase(_, p) => Some(scaled(&identity(2), -p)),
+            _ => None,
+        }
+    }
+
+    /// The phase, in radians, that the gate carries on top of the rotation it is
+    /// usually written as, i.e. the matrix is `exp(i phase)` times that rotation:
+    ///
+    /// | Gate                              | Rotation              | Phase            |
+    /// |-----------------------------------|-----------------------|------------------|
+    /// | X, Y, Z                           | RX, RY, RZ(pi)        | pi/2             |
+    /// | H                                 | exp(-i pi/2 (X+Z)/√2) | pi/2             |
+    /// | S, Sdag, SqrtX(dag), SqrtY(dag)   | RZ, RX, RY(±pi/2)     | ±pi/4            |
+    /// | T, Tdag                           | RZ(±pi/4)             | ±pi/8            |
+    /// | U1(lmd)                           | RZ(lmd)               | lmd/2            |
+    /// | U2(phi, lmd), U3(theta, phi, lmd) | RZ(phi) RY RZ(lmd)    | (phi + lmd)/2    |
+    /// | GlobalPhase(p)                    | identity              | p                |
+    ///
+    /// Every other gate has phase 0.
+    pub fn global_phase(&self) -> f64 {
+        match self {
+            Self::X(_) | Self::Y(_) | Self::Z(_) | Self::H(_) => FRAC_PI_2,
+            Self::S(_) | Self::SqrtX(_) | Self::SqrtY(_) => FRAC_PI_4,
+            Self::Sdag(_) | Self::SqrtXdag(_) | Self::SqrtYdag(_) => -FRAC_PI_4,
+            Self::T(_) => FRAC_PI_4 / 2.0,
+            Self::Tdag(_) => -FRAC_PI_4 / 2.0,
+            Self::U1(_, lmd) => lmd / 2.0,
+            Self::U2(_, phi, lmd) | Self::U3(_, _, phi, lmd) => (phi + lmd) / 2.0,
+            Self::GlobalPhase(_, p) => *p,
+            _ => 0.0,
+        }
+    }
+
+    pub fn matrix(&self) -> PyResult<Matrix> {
+        self.try_matrix().ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "{} gate does not have a unitary matrix.",
+                &self.clone().into_property().name
+            ))
+        })
+    }
+
+    /// Reference matrix for the gate's name in the `get_qubits()` order, including
+    /// the block-diagonal form of controlled gates. A matrix supplied together with
+    /// a known gate name is ignored, so the two can be compared.
+    pub fn expected_matrix(&self) -> PyResult<Matrix> {
+        match self {
+            Self::Other(prop) => {
+                let reference = GenericGateProperty {
+                    unitary_matrix: None,
+                    ..(**prop).clone()
+                };
+                match Self::from_property(reference)? {
+                    Some(gate) if !matches!(gate, Self::Other(_)) => gate.matrix(),
+                    _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "{} gate does not have a reference matrix.",
+                        &prop.name
+                    ))),
+                }
+            }
+            _ => self.matrix(),
+        }
+    }
+
+    /// Same as `matrix()` but returns `None` for gates without a unitary matrix.
+    pub fn try_matrix(&self) -> Option<Matrix> {
+        let half = Complex64::new(0.5, 0.0);
+        match self {
+            Self::Identity(_) => Some(identity(2)),
+            Self::X(_) => Some(pauli(1)),
+            Self::Y(_) => Some(pauli(2)),
+            Self::Z(_) => Some(pauli(3)),
+            Self::H(_) => {
+                let h = Complex64::new(FRAC_1_SQRT_2, 0.0);
+                Some(vec![vec![h, h], vec![h, -h]])
+            }
+            Self::S(_) => Some(diag(&[ONE, I])),
+            Self::Sdag(_) => Some(diag(&[ONE, -I])),
+            Self::SqrtX(_) => Some(vec![
+                vec![half * (ONE + I), half * (ONE - I)],
+                vec![half * (ONE - I), half * (ONE + I)],
+            ]),
+            Self::SqrtXdag(_) => Some(vec![
+                vec![half * (ONE - I), half * (ONE + I)],
+                vec![half * (ONE + I), half * (ONE - I)],
+            ]),
+            Self::SqrtY(_) => Some(vec![
+                vec![half * (ONE + I), -half * (ONE + I)],
+                vec![half * (ONE + I), half * (ONE + I)],
+            ]),
+            Self::SqrtYdag(_) => Some(vec![
+                vec![half * (ONE - I), half * (ONE - I)],
+                vec![-half * (ONE - I), half * (ONE - I)],
+            ]),
+            Self::T(_) => Some(diag(&[ONE, Complex64::from_polar(1.0, FRAC_PI_4)])),
+            Self::Tdag(_) => Some(diag(&[ONE, Complex64::from_polar(1.0, -FRAC_PI_4)])),
+            Self::RX(_, p) => Some(pauli_rotation(&[1], *p)),
+            Self::RY(_, p) => Some(pauli_rotation(&[2], *p)),
+            Self::RZ(_, p) => Some(pauli_rotation(&[3], *p)),
+            Self::U1(_, lmd) => Some(diag(&[ONE, Complex64::from_polar(1.0, *lmd)])),
+            Self::GlobalPhase(_, p) => {
+                let phase = Complex64::from_polar(1.0, *p);
+                Some(diag(&[phase, phase]))
+            }
+            Self::U2(_, phi, lmd) => Some(u3(FRAC_PI_2, *phi, *lmd)),
+            Self::U3(_, theta, phi, lmd) => Some(u3(*theta, *phi, *lmd)),
+            Self::CNOT(_, _) => Some(controlled(1, &pauli(1))),
+            Self::CZ(_, _) => Some(controlled(1, &pauli(3))),
+            Self::CRX(_, _, p) => Some(controlled(1, &pauli_rotation(&[1], *p))),
+            Self::CRY(_, _, p) => Some(controlled(1, &pauli_rotation(&[2], *p))),
+            Self::CRZ(_, _, p) => Some(controlled(1, &pauli_rotation(&[3], *p))),
+            Self::SWAP(_, _) => Some(vec![
+                vec![ONE, ZERO, ZERO, ZERO],
+                vec![ZERO, ZERO, ONE, ZERO],
+                vec![ZERO, ONE, ZERO, ZERO],
+                vec![ZERO, ZERO, ZERO, ONE],
+            ]),
+            Self::ISWAP(_, _) => Some(vec![
+                vec![ONE, ZERO, ZERO, ZERO],
+                vec![ZERO, ZERO, I, ZERO],
+                vec![ZERO, I, ZERO, ZERO],
+                vec![ZERO, ZERO, ZERO, ONE],
+            ]),
+            Self::SqrtISWAP(_, _) => {
+                let (c, s) = (FRAC_1_SQRT_2 * ONE, FRAC_1_SQRT_2 * I);
+                Some(vec![
+                    vec![ONE, ZERO, ZERO, ZERO],
+                    vec![ZERO, c, s, ZERO],
+                    vec![ZERO, s, c, ZERO],
+                    vec![ZERO, ZERO, ZERO, ONE],
+                ])
+            }
+            // (IX - XY) / sqrt(2) with the first qubit on the right, as in Qiskit.
+            Self::ECR(_, _) => {
+                let (a, b) = (pauli_string(&[1, 0]), pauli_string(&[2, 1]));
+                Some(
+                    a.iter()
+                        .zip(&b)
+                        .map(|(ra, rb)| {
+                            ra.iter()
+                                .zip(rb)
+                                .map(|(x, y)| (x - y) * FRAC_1_SQRT_2)
+                                .collect()
+                        })
+                        .collect(),
+                )
+            }
+            Self::TOFFOLI(_, _, _) => Some(controlled(2, &pauli(1))),
+            Self::UnitaryMatrix(_, mat) => Some(mat.clone()),
+            Self::Pauli(_, ps) => Some(pauli_string(ps)),
+            Self::PauliRotation(_, ps, a) => Some(pauli_rotation(ps, *a)),
+            Self::Measurement(_, _) | Self::Other(_) => None,
+        }
+    }
+}
+
+/// Fuses single-qubit gates acting on the same qubit, applied in order, into one
+/// UnitaryMatrix gate.
+#[pyfunction]
+#[pyo3(text_signature = "(gates: Sequence[QuantumGate])")]
+pub fn fuse_single_qubit(gates: Vec<QuantumGate>) -> PyResult<QuantumGate> {
+    let Some(first) = gates.first() else {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "At least one gate is required.",
+        ));
+    };
+    let qubits = first.get_qubits();
+    if qubits.len() != 1 || gates.iter().any(|g| g.get_qubits() != qubits) {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "All gates must act on the same single qubit.",
+        ));
+    }
+    let mut mat = identity(2);
+    for gate in gates.iter() {
+        mat = matmul(&gate.matrix()?, &mat);
+    }
+    Ok(QuantumGate::UnitaryMatrix(qubits, mat))
+}
+
+/// UnitaryMatrix gate with the matrix of U3(theta, phi, lam), i.e.
+/// RZ(phi) RY(theta) RZ(lam) up to a global phase.
+#[pyfunction(name = "u3")]
+#[pyo3(text_signature = "(target: int, theta: float, phi: float, lam: float)")]
+pub fn unitary_u3(target: usize, theta: f64, phi: f64, lam: f64) -> QuantumGate {
+    QuantumGate::UnitaryMatrix(vec![target], u3(theta, phi, lam))
+}
+
+/// UnitaryMatrix gate with the matrix of U2(phi, lam) = U3(pi/2, phi, lam).
+#[pyfunction(name = "u2")]
+#[pyo3(text_signature = "(target: int, phi: float, lam: float)")]
+pub fn unitary_u2(target: usize, phi: f64, lam: f64) -> QuantumGate {
+    unitary_u3(target, FRAC_PI_2, phi, lam)
+}
+
+/// UnitaryMatrix gate with the matrix of U1(lam) = U3(0, 0, lam).
+#[pyfunction(name = "u1")]
+#[pyo3(text_signature = "(target: int, lam: float)")]
+pub fn unitary_u1(target: usize, lam: f64) -> QuantumGate {
+    unitary_u3(target, 0.0, 0.0, lam)
+}
+
+/// The matrices of `gates` in one call, each as `QuantumGate.matrix()` returns
+/// it. A gate without a matrix raises `ValueError`, or gives `None` in its place
+/// when `skip_missing` is true so the result stays aligned with `gates`.
+#[pyfunction]
+#[pyo3(
+    signature = (gates, skip_missing=false),
+    text_signature = "(gates: Sequence[QuantumGate], skip_missing: bool = False)"
+)]
+pub fn matrices(
+    py: Python<'_>,
+    gates: Vec<QuantumGate>,
+    skip_missing: bool,
+) -> PyResult<Vec<Option<Bound<'_, PyTuple>>>> {
+    gates
+        .iter()
+        .map(|gate| {
+            let mat = match gate.try_matrix() {
+                Some(mat) => mat,
+                None if skip_missing => return Ok(None),
+                // Raises the same error as `QuantumGate.matrix()`.
+                None => gate.matrix()?,
+            };
+            Ok(Some(super::wrapper::matrix_to_py(py, mat)))
+        })
+        .collect()
+}
+
+/// UnitaryMatrix gate on the qubits of `gate_a` followed by those of `gate_b`,
+/// which must be disjoint.
+#[pyfunction]
+#[pyo3(text_signature = "(gate_a: QuantumGate, gate_b: QuantumGate)")]
+pub fn tensor(gate_a: QuantumGate, gate_b: QuantumGate) -> PyResult<QuantumGate> {
+    let (mut qubits, qubits_b) = (gate_a.get_qubits(), gate_b.get_qubits());
+    if qubits.iter().any(|q| qubits_b.contains(q)) {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Gates must act on disjoint qubits: {:?} and {:?}",
+            qubits, qubits_b
+        )));
+    }
+    let mat = kron(&gate_b.matrix()?, &gate_a.matrix()?);
+    qubits.extend(qubits_b);
+    Ok(QuantumGate::UnitaryMatrix(qubits, mat))
+}
+
+#[test]
+fn test_reverse_bit_order() {
+    // With the control as the most significant bit CNOT flips the last two rows.
+    let cnot = reverse_bit_order(&QuantumGate::CNOT(0, 1).try_matrix().unwrap());
+    let expected = [(0, 0), (1, 1), (2, 3), (3, 2)];
+    for (r, c) in expected {
+        assert_eq!(cnot[r][c], ONE);
+    }
+    let x = QuantumGate::X(0).try_matrix().unwrap();
+    assert_eq!(reverse_bit_order(&x), x);
+}
+
+#[test]
+fn test_power() {
+    let t = QuantumGate::T(0).try_matrix().unwrap();
+    let s = QuantumGate::S(0).try_matrix().unwrap();
+    let z = QuantumGate::Z(0).try_matrix().unwrap();
+    for (a, b) in power(&t, 2).iter().flatten().zip(s.iter().flatten()) {
+        assert!((a - b).norm() < 1e-12);
+    }
+    for (a, b) in power(&t, 4).iter().flatten().zip(z.iter().flatten()) {
+        assert!((a - b).norm() < 1e-12);
+    }
+    assert_eq!(power(&t, 0), identity(2));
+    let sqrt_iswap = QuantumGate::SqrtISWAP(0, 1).try_matrix().unwrap();
+    let iswap = QuantumGate::ISWAP(0, 1).try_matrix().unwrap();
+    for (a, b) in power(&sqrt_iswap, 2)
+        .iter()
+        .flatten()
+        .zip(iswap.iter().flatten())
+    {
+        assert!((a - b).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn test_equal_up_to_phase() {
+    let x = pauli(1);
+    let ix = vec![vec![ZERO, I], vec![I, ZERO]];
+    assert!(equal_up_to_phase(&x, &ix, 1e-12));
+    assert!(!equal_up_to_phase(&x, &pauli(2), 1e-12));
+    assert!(!equal_up_to_phase(&x, &identity(4), 1e-12));
+}
+
+#[test]
+fn test_commutes() {
+    let z = pauli(3);
+    let cnot = controlled(1, &pauli(1));
+    assert!(commutes(&z, &[0], &cnot, &[0, 1], 1e-12));
+    assert!(!commutes(&z, &[1], &cnot, &[0, 1], 1e-12));
+    assert!(commutes(&cnot, &[2, 1], &cnot, &[0, 1], 1e-12));
+}
+
+#[test]
+fn test_ecr_matrix() {
+    let ecr = QuantumGate::ECR(0, 1).try_matrix().unwrap();
+    let h = FRAC_1_SQRT_2 * ONE;
+    let expected = [
+        [ZERO, h, ZERO, h * I],
+        [h, ZERO, -h * I, ZERO],
+        [ZERO, h * I, ZERO, h],
+        [-h * I, ZERO, h, ZERO],
+    ];
+    for (a, b) in ecr.iter().flatten().zip(expected.iter().flatten()) {
+        assert!((a - b).norm() < 1e-12);
+    }
+    assert!(is_unitary(&ecr, 1e-12));
+    assert_eq!(ecr, dagger(&ecr));
+}
+
+#[test]
+fn test_controlled_matrix() {
+    let cnot = controlled(1, &pauli(1));
+    // |c=1, t=0> (index 1) and |c=1, t=1> (index 3) are swapped.
+    assert_eq!(cnot[1][3], ONE);
+    assert_eq!(cnot[3][1], ONE);
+    assert_eq!(cnot[2][2], ONE);
+    assert_eq!(cnot[1][1], ZERO);
+}
+
+#[test]
+fn test_pauli_rotation_matrix() {
+    // exp(-i pi/4 X0 Y1 Z2) = (I - i X0 Y1 Z2) / sqrt(2).
+    let ids = [1, 2, 3];
+    let rot = pauli_rotation(&ids, std::f64::consts::FRAC_PI_2);
+    let p = kron(&pauli(3), &kron(&pauli(2), &pauli(1)));
+    let expected: Matrix = (0..8)
+        .map(|i| {
+            (0..8)
+                .map(|j| (if i == j { ONE } else { ZERO } - I * p[i][j]) / 2f64.sqrt())
+                .collect()
+        })
+        .collect();
+    for (a, b) in rot.iter().flatten().zip(expected.iter().flatten()) {
+        assert!((a - b).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn test_process_fidelity() {
+    assert!((process_fidelity(&pauli(1), &pauli(1)) - 1.0).abs() < 1e-12);
+    assert!(process_fidelity(&pauli(1), &pauli(3)).abs() < 1e-12);
+    // Global phases do not matter.
+    let ix: Matrix = pauli(1)
+        .iter()
+        .map(|row| row.iter().map(|c| c * I).collect())
+        .collect();
+    assert!((process_fidelity(&pauli(1), &ix) - 1.0).abs() < 1e-12);
+    // cos^2(theta / 2) for RZ(theta) against the identity.
+    let rz = pauli_rotation(&[3], 0.6);
+    assert!((process_fidelity(&identity(2), &rz) - 0.3f64.cos().powi(2)).abs() < 1e-12);
+    assert!((average_gate_fidelity(&pauli(1), &pauli(3)) - 1.0 / 3.0).abs() < 1e-12);
+    for gate in [QuantumGate::H(0), QuantumGate::ECR(0, 1)] {
+        let mat = gate.try_matrix().unwrap();
+        assert!(process_fidelity(&mat, &mat) <= 1.0);
+        assert!(average_gate_fidelity(&mat, &mat) <= 1.0);
+    }
+}
+
+#[test]
+fn test_global_phase() {
+    use std::f64::consts::PI;
+    let rz = |a: f64| pauli_rotation(&[3], a);
+    let ry = |a: f64| pauli_rotation(&[2], a);
+    // exp(-i pi/2 (X+Z)/√2) = -i (X+Z)/√2.
+    let h_rotation: Matrix = QuantumGate::H(0)
+        .try_matrix()
+        .unwrap()
+        .iter()
+        .map(|row| row.iter().map(|c| -I * c).collect())
+        .collect();
+    let cases = [
+        (QuantumGate::X(0), pauli_rotation(&[1], PI)),
+        (QuantumGate::Y(0), ry(PI)),
+        (QuantumGate::Z(0), rz(PI)),
+        (QuantumGate::H(0), h_rotation),
+        (QuantumGate::S(0), rz(FRAC_PI_2)),
+        (QuantumGate::Sdag(0), rz(-FRAC_PI_2)),
+        (QuantumGate::T(0), rz(FRAC_PI_4)),
+        (QuantumGate::Tdag(0), rz(-FRAC_PI_4)),
+        (QuantumGate::SqrtX(0), pauli_rotation(&[1], FRAC_PI_2)),
+        (QuantumGate::SqrtYdag(0), ry(-FRAC_PI_2)),
+        (QuantumGate::U1(0, 0.3), rz(0.3)),
+        (
+            QuantumGate::U3(0, 0.3, 0.5, 0.7),
+            matmul(&matmul(&rz(0.5), &ry(0.3)), &rz(0.7)),
+        ),
+        (QuantumGate::GlobalPhase(0, 0.3), identity(2)),
+        (QuantumGate::RX(0, 0.3), pauli_rotation(&[1], 0.3)),
+    ];
+    for (gate, rotation) in cases {
+        let phase = Complex64::from_polar(1.0, gate.global_phase());
+        let mat = gate.try_matrix().unwrap();
+        for (a, b) in mat.iter().flatten().zip(rotation.iter().flatten()) {
+            assert!((a - phase * b).norm() < 1e-12, "{:?}", gate);
+        }
+    }
+}
+
+#[test]
+fn test_generator() {
+    assert_eq!(
+        QuantumGate::RX(0, 0.5).try_generator(),
+        Some(scaled(&pauli(1), 0.25))
+    );
+    assert_eq!(QuantumGate::H(0).try_generator(), None);
+    // Diagonal generators can be checked against the matrix entrywise.
+    for gate in [
+        QuantumGate::CRZ(0, 1, 0.5),
+        QuantumGate::U1(0, 0.5),
+        QuantumGate::GlobalPhase(0, 0.5),
+        QuantumGate::PauliRotation(vec![0, 1], vec![3, 3], 0.5),
+    ] {
+        let h = gate.try_generator().unwrap();
+        let mat = gate.try_matrix().unwrap();
+        for i in 0..mat.len() {
+            assert!((Complex64::from_polar(1.0, -h[i][i].re) - mat[i][i]).norm() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_to_coo() {
+    let cnot = QuantumGate::CNOT(0, 1).try_matrix().unwrap();
+    let (rows, cols, values) = to_coo(&cnot, 1e-12);
+    assert_eq!(rows, vec![0, 1, 2, 3]);
+    assert_eq!(cols, vec![0, 3, 2, 1]);
+    assert_eq!(values, vec![ONE; 4]);
+    let (rows, _, _) = to_coo(&diag(&[ONE, Complex64::new(1e-13, 0.0)]), 1e-12);
+    assert_eq!(rows, vec![0]);
+}
+
+#[test]
+fn test_permutation() {
+    // exp(-i pi/2 XX) = -i XX.
+    let xx = pauli_rotation(&[1, 1], std::f64::consts::PI);
+    assert!(is_permutation(&xx, 1e-12));
+    assert_eq!(nnz(&xx, 1e-12), 4);
+    assert!(is_permutation(&controlled(1, &pauli(2)), 1e-12));
+    let h: Matrix = vec![
+        vec![FRAC_1_SQRT_2 * ONE, FRAC_1_SQRT_2 * ONE],
+        vec![FRAC_1_SQRT_2 * ONE, -FRAC_1_SQRT_2 * ONE],
+    ];
+    assert!(!is_permutation(&h, 1e-12));
+    assert_eq!(nnz(&h, 1e-12), 4);
+    let mut doubled = identity(2);
+    doubled[1][0] = ONE;
+    doubled[1][1] = ZERO;
+    assert!(!is_permutation(&doubled, 1e-12));
+}
+
+#[test]
+fn test_basis_permutation() {
+    let toffoli = QuantumGate::TOFFOLI(0, 1, 2).try_matrix().unwrap();
+    assert_eq!(
+        basis_permutation(&toffoli, 1e-12),
+        Some(vec![0, 1, 2, 7, 4, 5, 6, 3])
+    );
+    let swap = QuantumGate::SWAP(0, 1).try_matrix().unwrap();
+    assert_eq!(basis_permutation(&swap, 1e-12), Some(vec![0, 2, 1, 3]));
+    assert_eq!(basis_permutation(&controlled(1, &pauli(3)), 1e-12), None);
+    assert_eq!(basis_permutation(&pauli(2), 1e-12), None);
+}
+
+#[test]
+fn test_is_identity() {
+    assert!(QuantumGate::RZ(0, 0.0).is_identity(1e-10, false));
+    assert!(QuantumGate::RX(0, -TAU).is_identity(1e-10, true));
+    assert!(!QuantumGate::RX(0, TAU).is_identity(1e-10, false));
+    assert!(QuantumGate::U1(0, TAU).is_identity(1e-10, false));
+    let phase = Complex64::from_polar(1.0, 0.3);
+    let near = vec![vec![phase, ZERO], vec![Complex64::new(1e-12, 0.0), phase]];
+    let gate = QuantumGate::UnitaryMatrix(vec![0], near);
+    assert!(gate.is_identity(1e-10, true));
+    assert!(!gate.is_identity(1e-10, false));
+    assert!(!QuantumGate::X(0).is_identity(1e-10, true));
+    assert!(QuantumGate::GlobalPhase(0, 0.3).is_identity(1e-10, true));
+    assert!(!QuantumGate::GlobalPhase(0, 0.3).is_identity(1e-10, false));
+}
+
+#[test]
+fn test_swapped_order() {
+    // The control of CNOT moves from bit 0 to bit 1.
+    let cnot = controlled(1, &pauli(1));
+    let swapped = embed(&cnot, &[0, 1], &[1, 0]);
+    assert_eq!(swapped[0b01], vec![ZERO, ONE, ZERO, ZERO]);
+    assert_eq!(swapped[0b10], vec![ZERO, ZERO, ZERO, ONE]);
+    assert_eq!(embed(&swapped, &[1, 0], &[0, 1]), cnot);
+}
+
+#[test]
+fn test_trace_and_determinant() {
+    let close = |a: Complex64, b: Complex64| (a - b).norm() < 1e-12;
+    assert!(close(trace(&identity(4)), 4.0 * ONE));
+    assert!(close(determinant(&pauli(1)), -ONE));
+    assert!(close(determinant(&pauli_rotation(&[1, 2], 0.7)), ONE));
+    // SWAP has eigenvalues 1, 1, 1, -1, and needs a row exchange.
+    let swap = QuantumGate::SWAP(0, 1).try_matrix().unwrap();
+    assert!(close(determinant(&swap), -ONE));
+    assert!(close(determinant(&controlled(1, &pauli(1))), -ONE));
+    assert!(close(determinant(&vec![vec![ZERO; 2]; 2]), ZERO));
+}
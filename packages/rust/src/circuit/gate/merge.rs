@@ -0,0 +1,71 @@
+use crate::circuit::gate::QuantumGate;
+use pyo3::prelude::*;
+
+impl QuantumGate<f64> {
+    /// The single rotation equal to applying `self` and then `other`, if both are
+    /// the same kind of rotation on the same qubits. PauliRotation gates must have
+    /// the same targets and Pauli ids in the same order.
+    pub fn merge_rotation(&self, other: &Self) -> Option<Self> {
+        match (self, other) {
+            (Self::RX(q0, a), Self::RX(q1, b)) if q0 == q1 => Some(Self::RX(*q0, a + b)),
+            (Self::RY(q0, a), Self::RY(q1, b)) if q0 == q1 => Some(Self::RY(*q0, a + b)),
+            (Self::RZ(q0, a), Self::RZ(q1, b)) if q0 == q1 => Some(Self::RZ(*q0, a + b)),
+            (Self::U1(q0, a), Self::U1(q1, b)) if q0 == q1 => Some(Self::U1(*q0, a + b)),
+            (Self::GlobalPhase(q0, a), Self::GlobalPhase(q1, b)) if q0 == q1 => {
+                Some(Self::GlobalPhase(*q0, a + b))
+            }
+            (Self::CRX(c0, t0, a), Self::CRX(c1, t1, b)) if (c0, t0) == (c1, t1) => {
+                Some(Self::CRX(*c0, *t0, a + b))
+            }
+            (Self::CRY(c0, t0, a), Self::CRY(c1, t1, b)) if (c0, t0) == (c1, t1) => {
+                Some(Self::CRY(*c0, *t0, a + b))
+            }
+            (Self::CRZ(c0, t0, a), Self::CRZ(c1, t1, b)) if (c0, t0) == (c1, t1) => {
+                Some(Self::CRZ(*c0, *t0, a + b))
+            }
+            (Self::PauliRotation(q0, p0, a), Self::PauliRotation(q1, p1, b))
+                if (q0, p0) == (q1, p1) =>
+            {
+                Some(Self::PauliRotation(q0.clone(), p0.clone(), a + b))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Combines `g1` followed by `g2` into one rotation, or returns `None` when they
+/// are not the same rotation on the same qubits.
+#[pyfunction]
+#[pyo3(text_signature = "(g1: QuantumGate, g2: QuantumGate)")]
+pub fn merge_rotations(g1: QuantumGate, g2: QuantumGate) -> Option<QuantumGate> {
+    g1.merge_rotation(&g2)
+}
+
+#[test]
+fn test_merge_rotation() {
+    assert_eq!(
+        QuantumGate::RZ(1, 0.25).merge_rotation(&QuantumGate::RZ(1, 0.5)),
+        Some(QuantumGate::RZ(1, 0.75))
+    );
+    assert_eq!(
+        QuantumGate::RZ(1, 0.25).merge_rotation(&QuantumGate::RZ(0, 0.5)),
+        None
+    );
+    assert_eq!(
+        QuantumGate::RZ(1, 0.25).merge_rotation(&QuantumGate::RX(1, 0.5)),
+        None
+    );
+    assert_eq!(
+        QuantumGate::CRX(0, 1, 0.25).merge_rotation(&QuantumGate::CRX(1, 0, 0.5)),
+        None
+    );
+    let rotation = QuantumGate::PauliRotation(vec![0, 2], vec![1, 3], 0.25);
+    assert_eq!(
+        rotation.merge_rotation(&QuantumGate::PauliRotation(vec![0, 2], vec![1, 3], 0.5)),
+        Some(QuantumGate::PauliRotation(vec![0, 2], vec![1, 3], 0.75))
+    );
+    assert_eq!(
+        rotation.merge_rotation(&QuantumGate::PauliRotation(vec![2, 0], vec![3, 1], 0.5)),
+        None
+    );
+}
@@ -0,0 +1,396 @@
+use crate::circuit::gate::{ParametricQuantumGate, QuantumGate, UnknownGateError};
+use crate::circuit::gates;
+use pyo3::prelude::*;
+
+/// Formats a parameter as an OpenQASM real literal, which requires a decimal point.
+fn format_real(v: f64) -> String {
+    let s = format!("{:?}", v);
+    match s.find('e') {
+        Some(pos) if !s[..pos].contains('.') => format!("{}.0{}", &s[..pos], &s[pos..]),
+        _ => s,
+    }
+}
+
+fn format_qubits(qubits: &[usize]) -> String {
+    qubits
+        .iter()
+        .map(|q| format!("q[{}]", q))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `symbol(params) qubits;`, leaving out the parentheses without parameters.
+fn format_statement(symbol: &str, params: &[String], qubits: &[usize]) -> String {
+    if params.is_empty() {
+        format!("{} {};", symbol, format_qubits(qubits))
+    } else {
+        format!(
+            "{}({}) {};",
+            symbol,
+            params.join(", "),
+            format_qubits(qubits)
+        )
+    }
+}
+
+impl QuantumGate<f64> {
+    /// Returns the OpenQASM 2.0 instruction for the gate, using the gates of
+    /// `qelib1.inc`. A Measurement gate yields one `measure` line per qubit.
+    pub fn to_qasm(&self) -> PyResult<String> {
+        let symbol = match self {
+            Self::Identity(_) => "id",
+            Self::X(_) => "x",
+            Self::Y(_) => "y",
+            Self::Z(_) => "z",
+            Self::H(_) => "h",
+            Self::S(_) => "s",
+            Self::Sdag(_) => "sdg",
+            Self::SqrtX(_) => "sx",
+            Self::SqrtXdag(_) => "sxdg",
+            Self::T(_) => "t",
+            Self::Tdag(_) => "tdg",
+            Self::RX(_, _) => "rx",
+            Self::RY(_, _) => "ry",
+            Self::RZ(_, _) => "rz",
+            Self::U1(_, _) => "u1",
+            Self::U2(_, _, _) => "u2",
+            Self::U3(_, _, _, _) => "u3",
+            Self::CNOT(_, _) => "cx",
+            Self::CZ(_, _) => "cz",
+            Self::CRX(_, _, _) => "crx",
+            Self::CRY(_, _, _) => "cry",
+            Self::CRZ(_, _, _) => "crz",
+            Self::SWAP(_, _) => "swap",
+            Self::TOFFOLI(_, _, _) => "ccx",
+            Self::Measurement(qs, cs) => {
+                return Ok(qs
+                    .iter()
+                    .zip(cs)
+                    .map(|(q, c)| format!("measure q[{}] -> c[{}];", q, c))
+                    .collect::<Vec<_>>()
+                    .join("\n"));
+            }
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "{} gate does not have an OpenQASM 2.0 representation.",
+                    &self.clone().into_property().name
+                )))
+            }
+        };
+        let params: Vec<String> = self
+            .clone()
+            .into_property()
+            .params
+            .into_iter()
+            .map(format_real)
+            .collect();
+        Ok(format_statement(symbol, &params, &self.get_qubits()))
+    }
+
+    /// Returns the OpenQASM 3 statement for the gate, using the gates of
+    /// `stdgates.inc`. GlobalPhase becomes the built-in `gphase`, which applies to
+    /// every qubit, and a Measurement yields one `c[i] = measure q[j];` line per
+    /// qubit.
+    pub fn to_qasm3(&self) -> PyResult<String> {
+        let symbol = match self {
+            Self::Identity(_) => "id",
+            Self::X(_) => "x",
+            Self::Y(_) => "y",
+            Self::Z(_) => "z",
+            Self::H(_) => "h",
+            Self::S(_) => "s",
+            Self::Sdag(_) => "sdg",
+            Self::SqrtX(_) => "sx",
+            Self::SqrtXdag(_) => "inv @ sx",
+            Self::T(_) => "t",
+            Self::Tdag(_) => "tdg",
+            Self::RX(_, _) => "rx",
+            Self::RY(_, _) => "ry",
+            Self::RZ(_, _) => "rz",
+            Self::U1(_, _) => "p",
+            Self::U2(_, _, _) => "u2",
+            Self::U3(_, _, _, _) => "u3",
+            Self::CNOT(_, _) => "cx",
+            Self::CZ(_, _) => "cz",
+            Self::CRX(_, _, _) => "crx",
+            Self::CRY(_, _, _) => "cry",
+            Self::CRZ(_, _, _) => "crz",
+            Self::SWAP(_, _) => "swap",
+            Self::TOFFOLI(_, _, _) => "ccx",
+            Self::GlobalPhase(_, p) => return Ok(format!("gphase({});", format_real(*p))),
+            Self::Measurement(qs, cs) => {
+                return Ok(qs
+                    .iter()
+                    .zip(cs)
+                    .map(|(q, c)| format!("c[{}] = measure q[{}];", c, q))
+                    .collect::<Vec<_>>()
+                    .join("\n"));
+            }
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "{} gate does not have an OpenQASM 3 representation.",
+                    &self.clone().into_property().name
+                )))
+            }
+        };
+        let params: Vec<String> = self
+            .clone()
+            .into_property()
+            .params
+            .into_iter()
+            .map(format_real)
+            .collect();
+        Ok(format_statement(symbol, &params, &self.get_qubits()))
+    }
+}
+
+impl ParametricQuantumGate {
+    /// Returns the OpenQASM 3 statement for the gate with its parameter written as
+    /// the identifier `param_name`, e.g. `rx(theta) q[0];`.
+    pub fn to_qasm3(&self, param_name: &str) -> PyResult<String> {
+        let is_identifier = param_name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && param_name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_identifier {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "'{}' is not a valid OpenQASM 3 identifier.",
+                param_name
+            )));
+        }
+        let symbol = match self.0.name.as_str() {
+            "ParametricRX" => "rx",
+            "ParametricRY" => "ry",
+            "ParametricRZ" => "rz",
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "{} gate does not have an OpenQASM 3 representation.",
+                    &self.0.name
+                )))
+            }
+        };
+        Ok(format_statement(
+            symbol,
+            &[param_name.to_owned()],
+            &self.0.target_indices,
+        ))
+    }
+}
+
+fn parse_message(line: &str, reason: &str) -> String {
+    format!("Cannot parse OpenQASM instruction '{}': {}", line, reason)
+}
+
+fn parse_error(line: &str, reason: &str) -> PyErr {
+    pyo3::exceptions::PyValueError::new_err(parse_message(line, reason))
+}
+
+/// Parses a product or quotient of real literals and `pi`, e.g. `-3*pi/4`. Empty
+/// factors and non-finite values such as `nan`, `inf` or `1/0` are rejected.
+fn parse_real(expr: &str) -> Option<f64> {
+    let (sign, expr) = match expr.strip_prefix('-') {
+        Some(rest) => (-1.0, rest),
+        None => (1.0, expr.strip_prefix('+').unwrap_or(expr)),
+    };
+    if expr.is_empty() || expr.ends_with(['*', '/']) {
+        return None;
+    }
+    let mut value = sign;
+    let mut op = '*';
+    for token in expr.split_inclusive(['*', '/']) {
+        let (factor, next) = match token.strip_suffix(['*', '/']) {
+            Some(f) => (f, token.chars().last().unwrap()),
+            None => (token, '*'),
+        };
+        let factor = match factor.trim() {
+            "" => return None,
+            "pi" => std::f64::consts::PI,
+            f => f.parse::<f64>().ok().filter(|f| f.is_finite())?,
+        };
+        value = if op == '*' {
+            value * factor
+        } else {
+            value / factor
+        };
+        op = next;
+    }
+    value.is_finite().then_some(value)
+}
+
+/// Parses a qubit or bit reference such as `q[3]`.
+fn parse_index(arg: &str) -> Option<usize> {
+    let (reg, rest) = arg.split_once('[')?;
+    let index = rest.strip_suffix(']')?;
+    if reg.is_empty() || !reg.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    index.parse().ok()
+}
+
+impl QuantumGate<f64> {
+    /// Parses a single OpenQASM 2.0 instruction generated by `to_qasm()`.
+    pub fn from_qasm(line: &str) -> PyResult<Self> {
+        let body = line.trim();
+        let body = body.strip_suffix(';').unwrap_or(body).trim_end();
+        let name_len = body
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(body.len());
+        let (name, mut rest) = body.split_at(name_len);
+        rest = rest.trim_start();
+
+        let mut params = vec![];
+        if let Some(inner) = rest.strip_prefix('(') {
+            let (args, tail) = inner
+                .split_once(')')
+                .ok_or_else(|| parse_error(line, "unclosed parameter list"))?;
+            for p in args.split(',') {
+                let p: String = p.split_whitespace().collect();
+                params.push(
+                    parse_real(&p)
+                        .ok_or_else(|| parse_error(line, &format!("invalid parameter '{}'", p)))?,
+                );
+            }
+            rest = tail;
+        }
+        let rest: String = rest.split_whitespace().collect();
+
+        if name == "measure" {
+            let (q, c) = rest
+                .split_once("->")
+                .ok_or_else(|| parse_error(line, "missing '->' in measure"))?;
+            let q = parse_index(q).ok_or_else(|| parse_error(line, "malformed qubit reference"))?;
+            let c = parse_index(c).ok_or_else(|| parse_error(line, "malformed bit reference"))?;
+            return Ok(Self::Measurement(vec![q], vec![c]));
+        }
+
+        let qubits = rest
+            .split(',')
+            .map(parse_index)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| parse_error(line, "malformed qubit reference"))?;
+        build_qelib1(name, &params, &qubits, |reason| parse_message(line, reason))
+    }
+
+    /// Builds a gate from a Qiskit gate name, e.g. `cx` or `u`, with the qubits
+    /// and parameters in Qiskit's order. Besides the `qelib1.inc` names, the
+    /// aliases `i`, `p`, `u`, `cnot` and `toffoli` and the IBM native gate `ecr`
+    /// are accepted.
+    pub fn from_qiskit(name: &str, qubits: &[usize], params: &[f64]) -> PyResult<Self> {
+        if name == "ecr" {
+            return match (params, qubits) {
+                ([], [q0, q1]) => gates::ecr(*q0, *q1),
+                _ => Err(pyo3::exceptions::PyValueError::new_err(
+                    "Cannot convert Qiskit gate 'ecr': ecr takes no parameters and acts on 2 qubits",
+                )),
+            };
+        }
+        let qelib1_name = match name {
+            "i" => "id",
+            "p" => "u1",
+            "u" => "u3",
+            "cnot" => "cx",
+            "toffoli" => "ccx",
+            name => name,
+        };
+        build_qelib1(qelib1_name, params, qubits, |reason| {
+            format!("Cannot convert Qiskit gate '{}': {}", name, reason)
+        })
+    }
+}
+
+/// Builds a gate of `qelib1.inc`, reporting invalid input through `error`.
+fn build_qelib1(
+    name: &str,
+    params: &[f64],
+    qubits: &[usize],
+    message: impl Fn(&str) -> String,
+) -> PyResult<QuantumGate> {
+    let error = |reason: &str| pyo3::exceptions::PyValueError::new_err(message(reason));
+    let (param_count, qubit_count) = match name {
+        "id" | "x" | "y" | "z" | "h" | "s" | "sdg" | "sx" | "sxdg" | "t" | "tdg" => (0, 1),
+        "rx" | "ry" | "rz" | "u1" => (1, 1),
+        "u2" => (2, 1),
+        "u3" => (3, 1),
+        "cx" | "cz" | "swap" => (0, 2),
+        "crx" | "cry" | "crz" => (1, 2),
+        "ccx" => (0, 3),
+        _ => {
+            return Err(UnknownGateError::new_err(message(&format!(
+                "unknown gate '{}', expected one of id, x, y, z, h, s, sdg, sx, sxdg, t, tdg, \
+                 rx, ry, rz, u1, u2, u3, cx, cz, crx, cry, crz, swap and ccx",
+                name
+            ))))
+        }
+    };
+    if params.len() != param_count {
+        return Err(error(&format!(
+            "{} takes {} parameter(s)",
+            name, param_count
+        )));
+    }
+    if qubits.len() != qubit_count {
+        return Err(error(&format!("{} acts on {} qubit(s)", name, qubit_count)));
+    }
+
+    let (p, q) = (params, qubits);
+    match name {
+        "id" => Ok(QuantumGate::Identity(q[0])),
+        "x" => Ok(QuantumGate::X(q[0])),
+        "y" => Ok(QuantumGate::Y(q[0])),
+        "z" => Ok(QuantumGate::Z(q[0])),
+        "h" => Ok(QuantumGate::H(q[0])),
+        "s" => Ok(QuantumGate::S(q[0])),
+        "sdg" => Ok(QuantumGate::Sdag(q[0])),
+        "sx" => Ok(QuantumGate::SqrtX(q[0])),
+        "sxdg" => Ok(QuantumGate::SqrtXdag(q[0])),
+        "t" => Ok(QuantumGate::T(q[0])),
+        "tdg" => Ok(QuantumGate::Tdag(q[0])),
+        "rx" => Ok(QuantumGate::RX(q[0], p[0])),
+        "ry" => Ok(QuantumGate::RY(q[0], p[0])),
+        "rz" => Ok(QuantumGate::RZ(q[0], p[0])),
+        "u1" => Ok(QuantumGate::U1(q[0], p[0])),
+        "u2" => Ok(QuantumGate::U2(q[0], p[0], p[1])),
+        "u3" => Ok(QuantumGate::U3(q[0], p[0], p[1], p[2])),
+        "cx" => gates::cnot(q[0], q[1]),
+        "cz" => gates::cz(q[0], q[1]),
+        "crx" => gates::crx(q[0], q[1], p[0]),
+        "cry" => gates::cry(q[0], q[1], p[0]),
+        "crz" => gates::crz(q[0], q[1], p[0]),
+        "swap" => gates::swap(q[0], q[1]),
+        _ => gates::toffoli(q[0], q[1], q[2]),
+    }
+}
+
+#[test]
+fn test_parse_real() {
+    assert_eq!(parse_real("0.5"), Some(0.5));
+    assert_eq!(parse_real("-pi/2"), Some(-std::f64::consts::FRAC_PI_2));
+    assert_eq!(parse_real("3*pi/4"), Some(3.0 * std::f64::consts::PI / 4.0));
+    assert_eq!(parse_real("1.0e-3"), Some(1.0e-3));
+    assert_eq!(parse_real("pi*"), None);
+    for expr in [
+        "",
+        "-",
+        "pi**2",
+        "nan",
+        "inf",
+        "-infinity",
+        "1/0",
+        "1e308*10",
+    ] {
+        assert_eq!(parse_real(expr), None, "{}", expr);
+    }
+}
+
+#[test]
+fn test_format_statement() {
+    assert_eq!(format_statement("cx", &[], &[0, 1]), "cx q[0], q[1];");
+    let params = [format_real(1e-300), "theta".to_owned()];
+    assert_eq!(
+        format_statement("u2", &params, &[3]),
+        "u2(1.0e-300, theta) q[3];"
+    );
+}
@@ -0,0 +1,53 @@
+use crate::circuit::gate::QuantumGate;
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+
+/// Number of gates of each name, ordered by name.
+#[pyfunction]
+#[pyo3(text_signature = "(gates: Sequence[QuantumGate])")]
+pub fn count_by_name(gates: Vec<QuantumGate>) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for gate in gates {
+        *counts.entry(gate.into_property().name).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Number of T and Tdag gates.
+#[pyfunction]
+#[pyo3(text_signature = "(gates: Sequence[QuantumGate])")]
+pub fn t_count(gates: Vec<QuantumGate>) -> usize {
+    gates
+        .iter()
+        .filter(|g| matches!(g, QuantumGate::T(_) | QuantumGate::Tdag(_)))
+        .count()
+}
+
+/// Number of gates acting on exactly two qubits, counting controls.
+#[pyfunction]
+#[pyo3(text_signature = "(gates: Sequence[QuantumGate])")]
+pub fn two_qubit_count(gates: Vec<QuantumGate>) -> usize {
+    gates
+        .iter()
+        .filter(|g| g.sorted_qubits().len() == 2)
+        .count()
+}
+
+#[test]
+fn test_resource_counts() {
+    let gates = vec![
+        QuantumGate::T(0),
+        QuantumGate::H(1),
+        QuantumGate::CNOT(0, 1),
+        QuantumGate::Tdag(1),
+        QuantumGate::T(2),
+        QuantumGate::TOFFOLI(0, 1, 2),
+        QuantumGate::Pauli(vec![0, 3], vec![1, 3]),
+    ];
+    let counts = count_by_name(gates.clone());
+    assert_eq!(counts["T"], 2);
+    assert_eq!(counts["CNOT"], 1);
+    assert_eq!(counts.len(), 6);
+    assert_eq!(t_count(gates.clone()), 3);
+    assert_eq!(two_qubit_count(gates), 2);
+}
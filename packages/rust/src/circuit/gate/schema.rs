@@ -0,0 +1,193 @@
+use crate::circuit::gate::GenericGateProperty;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// Number of entries a gate takes in one of its index or Pauli id fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Count {
+    Fixed(usize),
+    /// Any number of entries, including none.
+    Any,
+    /// One entry per target qubit.
+    PerTarget,
+}
+
+impl Count {
+    fn accepts(self, len: usize, target_count: usize) -> bool {
+        match self {
+            Count::Fixed(n) => len == n,
+            Count::Any => true,
+            Count::PerTarget => len == target_count,
+        }
+    }
+
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            Count::Fixed(n) => n.into_py(py),
+            Count::Any => "any".into_py(py),
+            Count::PerTarget => "per_target".into_py(py),
+        }
+    }
+}
+
+/// The fields a gate name needs to form a named `QuantumGate` variant.
+#[derive(Clone, Copy, Debug)]
+pub struct GateSignature {
+    pub name: &'static str,
+    pub targets: Count,
+    pub controls: usize,
+    pub classical: Count,
+    pub params: usize,
+    pub pauli_ids: Count,
+    /// Whether the `unitary_matrix` field is required.
+    pub unitary_matrix: bool,
+    /// Whether the gate has a matrix, i.e. is not a measurement.
+    pub has_matrix: bool,
+}
+
+impl GateSignature {
+    const fn fixed(name: &'static str, targets: usize, controls: usize, params: usize) -> Self {
+        GateSignature {
+            name,
+            targets: Count::Fixed(targets),
+            controls,
+            classical: Count::Fixed(0),
+            params,
+            pauli_ids: Count::Fixed(0),
+            unitary_matrix: false,
+            has_matrix: true,
+        }
+    }
+
+    pub fn accepts(&self, prop: &GenericGateProperty) -> bool {
+        let targets = prop.target_indices.len();
+        self.targets.accepts(targets, targets)
+            && prop.control_indices.len() == self.controls
+            && self
+                .classical
+                .accepts(prop.classical_indices.len(), targets)
+            && prop.params.len() == self.params
+            && self.pauli_ids.accepts(prop.pauli_ids.len(), targets)
+            && prop.unitary_matrix.is_some() == self.unitary_matrix
+    }
+}
+
+/// Every gate name `QuantumGate::from_property` turns into a named variant, with
+/// the fields it requires. Properties that match none of them become `Other`.
+pub const GATE_SIGNATURES: &[GateSignature] = &[
+    GateSignature::fixed("Identity", 1, 0, 0),
+    GateSignature::fixed("X", 1, 0, 0),
+    GateSignature::fixed("Y", 1, 0, 0),
+    GateSignature::fixed("Z", 1, 0, 0),
+    GateSignature::fixed("H", 1, 0, 0),
+    GateSignature::fixed("S", 1, 0, 0),
+    GateSignature::fixed("Sdag", 1, 0, 0),
+    GateSignature::fixed("SqrtX", 1, 0, 0),
+    GateSignature::fixed("SqrtXdag", 1, 0, 0),
+    GateSignature::fixed("SqrtY", 1, 0, 0),
+    GateSignature::fixed("SqrtYdag", 1, 0, 0),
+    GateSignature::fixed("T", 1, 0, 0),
+    GateSignature::fixed("Tdag", 1, 0, 0),
+    GateSignature::fixed("RX", 1, 0, 1),
+    GateSignature::fixed("RY", 1, 0, 1),
+    GateSignature::fixed("RZ", 1, 0, 1),
+    GateSignature::fixed("U1", 1, 0, 1),
+    GateSignature::fixed("U2", 1, 0, 2),
+    GateSignature::fixed("U3", 1, 0, 3),
+    GateSignature::fixed("GlobalPhase", 1, 0, 1),
+    GateSignature::fixed("CNOT", 1, 1, 0),
+    GateSignature::fixed("CZ", 1, 1, 0),
+    GateSignature::fixed("CRX", 1, 1, 1),
+    GateSignature::fixed("CRY", 1, 1, 1),
+    GateSignature::fixed("CRZ", 1, 1, 1),
+    GateSignature::fixed("SWAP", 2, 0, 0),
+    GateSignature::fixed("ISWAP", 2, 0, 0),
+    GateSignature::fixed("SqrtISWAP", 2, 0, 0),
+    GateSignature::fixed("ECR", 2, 0, 0),
+    GateSignature::fixed("TOFFOLI", 1, 2, 0),
+    GateSignature {
+        targets: Count::Any,
+        unitary_matrix: true,
+        ..GateSignature::fixed("UnitaryMatrix", 0, 0, 0)
+    },
+    GateSignature {
+        targets: Count::Any,
+        pauli_ids: Count::PerTarget,
+        ..GateSignature::fixed("Pauli", 0, 0, 0)
+    },
+    GateSignature {
+        targets: Count::Any,
+        pauli_ids: Count::PerTarget,
+        ..GateSignature::fixed("PauliRotation", 0, 0, 1)
+    },
+    GateSignature {
+        targets: Count::Any,
+        classical: Count::PerTarget,
+        has_matrix: false,
+        ..GateSignature::fixed("Measurement", 0, 0, 0)
+    },
+];
+
+/// The signature `prop` matches, if its name is known and its fields fit.
+pub fn find_signature(prop: &GenericGateProperty) -> Option<&'static GateSignature> {
+    GATE_SIGNATURES
+        .iter()
+        .find(|s| s.name == prop.name && s.accepts(prop))
+}
+
+/// Describes every gate name the constructor recognizes as a dict with keys
+/// `name`, `targets`, `controls`, `classical`, `params`, `pauli_ids`,
+/// `unitary_matrix` and `has_matrix`. Counts are ints, `"any"` or `"per_target"`.
+#[pyfunction]
+#[pyo3(text_signature = "()")]
+pub fn supported_gates(py: Python<'_>) -> PyResult<Vec<Bound<'_, PyDict>>> {
+    GATE_SIGNATURES
+        .iter()
+        .map(|s| {
+            let dict = PyDict::new_bound(py);
+            dict.set_item("name", s.name)?;
+            dict.set_item("targets", s.targets.into_py(py))?;
+            dict.set_item("controls", s.controls)?;
+            dict.set_item("classical", s.classical.into_py(py))?;
+            dict.set_item("params", s.params)?;
+            dict.set_item("pauli_ids", s.pauli_ids.into_py(py))?;
+            dict.set_item("unitary_matrix", s.unitary_matrix)?;
+            dict.set_item("has_matrix", s.has_matrix)?;
+            Ok(dict)
+        })
+        .collect()
+}
+
+#[test]
+fn test_find_signature() {
+    for s in GATE_SIGNATURES {
+        let count = |c: Count| match c {
+            Count::Fixed(n) => n,
+            Count::Any | Count::PerTarget => 2,
+        };
+        let prop = GenericGateProperty {
+            name: s.name.to_owned(),
+            target_indices: (0..count(s.targets)).collect(),
+            control_indices: (10..10 + s.controls).collect(),
+            classical_indices: (0..count(s.classical)).collect(),
+            params: vec![0.5; s.params],
+            pauli_ids: vec![1; count(s.pauli_ids)],
+            unitary_matrix: s
+                .unitary_matrix
+                .then(|| crate::circuit::gate::matrix::identity(4)),
+        };
+        assert_eq!(find_signature(&prop).map(|f| f.name), Some(s.name));
+        let extra_param = GenericGateProperty {
+            params: vec![0.5; s.params + 1],
+            ..prop
+        };
+        assert!(find_signature(&extra_param).is_none(), "{}", s.name);
+    }
+    let measurement = GenericGateProperty {
+        name: "Measurement".to_owned(),
+        target_indices: vec![0, 1],
+        classical_indices: vec![0],
+        ..Default::default()
+    };
+    assert!(find_signature(&measurement).is_none());
+}
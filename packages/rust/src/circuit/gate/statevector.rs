@@ -0,0 +1,189 @@
+use crate::circuit::gate::matrix::{self, Matrix};
+use crate::circuit::gate::QuantumGate;
+use num_complex::Complex64;
+use pyo3::buffer::PyBuffer;
+use pyo3::prelude::*;
+use std::cell::Cell;
+
+/// Statevector storage that gates can be applied to.
+trait Amplitudes {
+    fn dim(&self) -> usize;
+    fn amplitude(&self, i: usize) -> Complex64;
+    fn set_amplitude(&mut self, i: usize, a: Complex64);
+}
+
+impl Amplitudes for [Complex64] {
+    fn dim(&self) -> usize {
+        self.len()
+    }
+
+    fn amplitude(&self, i: usize) -> Complex64 {
+        self[i]
+    }
+
+    fn set_amplitude(&mut self, i: usize, a: Complex64) {
+        self[i] = a;
+    }
+}
+
+/// The cells of a complex128 NumPy buffer viewed as float64, so every amplitude
+/// is a real part followed by an imaginary part.
+struct InterleavedCells<'a>(&'a [Cell<f64>]);
+
+impl Amplitudes for InterleavedCells<'_> {
+    fn dim(&self) -> usize {
+        self.0.len() / 2
+    }
+
+    fn amplitude(&self, i: usize) -> Complex64 {
+        Complex64::new(self.0[2 * i].get(), self.0[2 * i + 1].get())
+    }
+
+    fn set_amplitude(&mut self, i: usize, a: Complex64) {
+        self.0[2 * i].set(a.re);
+        self.0[2 * i + 1].set(a.im);
+    }
+}
+
+/// Applies `mat` on `targets` to the amplitudes whose `controls` bits are all 1.
+/// Qubit i is the i-th bit of the basis index, as in the gate matrices.
+fn apply_controlled_to<S: Amplitudes + ?Sized>(
+    state: &mut S,
+    controls: &[usize],
+    targets: &[usize],
+    mat: &Matrix,
+) {
+    let control_mask = controls.iter().fold(0usize, |acc, q| acc | 1 << q);
+    let target_mask = targets.iter().fold(0usize, |acc, q| acc | 1 << q);
+    // Offset of the k-th column of `mat` from the base index with all targets 0.
+    let offsets: Vec<usize> = (0..mat.len())
+        .map(|k| {
+            targets
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (bit, q)| acc | ((k >> bit) & 1) << q)
+        })
+        .collect();
+    let mut amplitudes = vec![Complex64::new(0.0, 0.0); mat.len()];
+    for base in 0..state.dim() {
+        if base & target_mask != 0 || base & control_mask != control_mask {
+            continue;
+        }
+        for (a, offset) in amplitudes.iter_mut().zip(&offsets) {
+            *a = state.amplitude(base | offset);
+        }
+        for (row, offset) in mat.iter().zip(&offsets) {
+            state.set_amplitude(
+                base | offset,
+                row.iter().zip(&amplitudes).map(|(m, a)| m * a).sum(),
+            );
+        }
+    }
+}
+
+impl QuantumGate<f64> {
+    /// Splits the gate into its control qubits and the matrix applied to the
+    /// targets when all controls are 1.
+    fn controlled_action(&self) -> Option<(Vec<usize>, Vec<usize>, Matrix)> {
+        match self {
+            Self::CNOT(c, t) => Some((vec![*c], vec![*t], matrix::pauli(1))),
+            Self::CZ(c, t) => Some((vec![*c], vec![*t], matrix::pauli(3))),
+            Self::CRX(c, t, p) => Some((vec![*c], vec![*t], matrix::pauli_rotation(&[1], *p))),
+            Self::CRY(c, t, p) => Some((vec![*c], vec![*t], matrix::pauli_rotation(&[2], *p))),
+            Self::CRZ(c, t, p) => Some((vec![*c], vec![*t], matrix::pauli_rotation(&[3], *p))),
+            Self::TOFFOLI(c1, c2, t) => Some((vec![*c1, *c2], vec![*t], matrix::pauli(1))),
+            _ => Some((vec![], self.get_qubits(), self.try_matrix()?)),
+        }
+    }
+
+    /// Applies the gate to the amplitudes of a `2^n` dimensional statevector.
+    pub fn apply_to(&self, state: &mut [Complex64]) -> Result<(), String> {
+        self.apply_to_amplitudes(state)
+    }
+
+    fn apply_to_amplitudes<S: Amplitudes + ?Sized>(&self, state: &mut S) -> Result<(), String> {
+        let name = || self.clone().into_property().name;
+        let (controls, targets, mat) = self
+            .controlled_action()
+            .ok_or_else(|| format!("{} gate does not have a unitary matrix.", name()))?;
+        if let Some(q) = controls.iter().chain(&targets).find(|q| {
+            1usize
+                .checked_shl(**q as u32)
+                .is_none_or(|b| b >= state.dim())
+        }) {
+            return Err(format!(
+                "{} gate acts on qubit {} outside of the statevector.",
+                name(),
+                q
+            ));
+        }
+        apply_controlled_to(state, &controls, &targets, &mat);
+        Ok(())
+    }
+}
+
+/// Applies `gate` in place to `state`, a C-contiguous complex128 NumPy array of
+/// length `2**num_qubits`.
+#[pyfunction]
+#[pyo3(text_signature = "(state: npt.NDArray[np.complex128], gate: QuantumGate, num_qubits: int)")]
+pub fn apply_gate(state: &Bound<'_, PyAny>, gate: QuantumGate, num_qubits: usize) -> PyResult<()> {
+    let py = state.py();
+    let numpy = py.import_bound("numpy")?;
+    if !state.getattr("dtype")?.eq(numpy.getattr("complex128")?)? {
+        return Err(pyo3::exceptions::PyTypeError::new_err(
+            "The statevector must have dtype complex128.",
+        ));
+    }
+    let view = state.call_method1("view", (numpy.getattr("float64")?,))?;
+    let buffer = PyBuffer::<f64>::get_bound(&view)?;
+    let cells = buffer.as_mut_slice(py).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(
+            "The statevector must be a writable C-contiguous array.",
+        )
+    })?;
+    let dim = 1usize.checked_shl(num_qubits as u32).unwrap_or(0);
+    if cells.len() != 2 * dim {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "The statevector must have 2**{} entries.",
+            num_qubits
+        )));
+    }
+    gate.apply_to_amplitudes(&mut InterleavedCells(cells))
+        .map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
+#[test]
+fn test_apply_controlled() {
+    let one = Complex64::new(1.0, 0.0);
+    let zero = Complex64::new(0.0, 0.0);
+    // |q2 q1 q0> = |011>.
+    let mut initial = vec![zero; 8];
+    initial[0b011] = one;
+    // TOFFOLI(0, 2, 1) does nothing because q2 is 0.
+    let mut state = initial.clone();
+    QuantumGate::TOFFOLI(0, 2, 1).apply_to(&mut state).unwrap();
+    assert_eq!(state, initial);
+    // CNOT(1, 2) flips q2, both through the target matrix and the full matrix.
+    QuantumGate::CNOT(1, 2).apply_to(&mut state).unwrap();
+    assert_eq!(state[0b111], one);
+    let mut dense = initial.clone();
+    let cnot = matrix::controlled(1, &matrix::pauli(1));
+    apply_controlled_to(&mut dense[..], &[], &[1, 2], &cnot);
+    assert_eq!(dense, state);
+    assert!(QuantumGate::X(3).apply_to(&mut state).is_err());
+}
+
+#[test]
+fn test_apply_to_interleaved_cells() {
+    let h = std::f64::consts::FRAC_1_SQRT_2;
+    // |q1 q0> = |01>, stored as the real and imaginary parts of each amplitude.
+    let flat = [0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0].map(Cell::new);
+    let gate = QuantumGate::CRY(0, 1, std::f64::consts::FRAC_PI_2);
+    gate.apply_to_amplitudes(&mut InterleavedCells(&flat))
+        .unwrap();
+    let values: Vec<f64> = flat.iter().map(Cell::get).collect();
+    assert!(values
+        .iter()
+        .zip([0.0, 0.0, h, 0.0, 0.0, 0.0, h, 0.0])
+        .all(|(a, b)| (a - b).abs() < 1e-12));
+}
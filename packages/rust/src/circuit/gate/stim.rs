@@ -0,0 +1,58 @@
+use crate::circuit::gate::QuantumGate;
+use pyo3::prelude::*;
+
+impl QuantumGate<f64> {
+    /// Stim instruction for a Clifford gate or a measurement, e.g. `CX 0 1` or
+    /// `M 0 2`, with the qubits in `get_qubits()` order. A Pauli gate becomes one
+    /// instruction per qubit. Stim records measurement results in order, so the
+    /// classical indices of a measurement are not part of the instruction.
+    pub fn to_stim(&self) -> PyResult<String> {
+        let name = match self {
+            Self::Identity(_) => "I",
+            Self::X(_) => "X",
+            Self::Y(_) => "Y",
+            Self::Z(_) => "Z",
+            Self::H(_) => "H",
+            Self::S(_) => "S",
+            Self::Sdag(_) => "S_DAG",
+            Self::SqrtX(_) => "SQRT_X",
+            Self::SqrtXdag(_) => "SQRT_X_DAG",
+            Self::SqrtY(_) => "SQRT_Y",
+            Self::SqrtYdag(_) => "SQRT_Y_DAG",
+            Self::CNOT(_, _) => "CX",
+            Self::CZ(_, _) => "CZ",
+            Self::SWAP(_, _) => "SWAP",
+            Self::ISWAP(_, _) => "ISWAP",
+            Self::Measurement(_, _) => "M",
+            Self::Pauli(qs, ps) => {
+                if qs.len() != ps.len() {
+                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                        "Pauli gate has {} Pauli ids for {} qubits.",
+                        ps.len(),
+                        qs.len()
+                    )));
+                }
+                return Ok(qs
+                    .iter()
+                    .zip(ps)
+                    .map(|(q, p)| match ["I", "X", "Y", "Z"].get(*p as usize) {
+                        Some(symbol) => Ok(format!("{} {}", symbol, q)),
+                        None => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                            "Invalid Pauli id {} for qubit {}.",
+                            p, q
+                        ))),
+                    })
+                    .collect::<PyResult<Vec<_>>>()?
+                    .join("\n"));
+            }
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "{} gate does not have a Stim equivalent.",
+                    &self.clone().into_property().name
+                )))
+            }
+        };
+        let qubits: Vec<String> = self.get_qubits().iter().map(|q| q.to_string()).collect();
+        Ok(format!("{} {}", name, qubits.join(" ")))
+    }
+}
@@ -1,9 +1,69 @@
-use crate::circuit::gate::{GenericGateProperty, ParametricQuantumGate, QuantumGate};
+use crate::circuit::gate::{
+    GenericGateProperty, IndexConflictError, InvalidUnitaryError, ParametricQuantumGate,
+    QuantumGate,
+};
 use crate::circuit::parameter::Wrapper;
 use crate::circuit::MaybeUnbound;
-use num_complex::{Complex64, ComplexFloat};
+use num_complex::Complex64;
 use pyo3::prelude::*;
 
+pub(crate) fn check_distinct_indices(name: &str, indices: &[usize]) -> PyResult<()> {
+    if indices
+        .iter()
+        .enumerate()
+        .any(|(i, q)| indices[..i].contains(q))
+    {
+        Err(IndexConflictError::new_err(format!(
+            "{} gate cannot act on the same qubit more than once: {:?}",
+            name, indices
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn check_pauli_id_range(name: &str, pauli_ids: &[u8], min_id: u8) -> PyResult<()> {
+    match pauli_ids.iter().find(|p| !(min_id..=3).contains(*p)) {
+        Some(p) => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "{} gate accepts only Pauli ids {}, got {}",
+            name,
+            if min_id == 0 {
+                "0 (I), 1 (X), 2 (Y) and 3 (Z)"
+            } else {
+                "1 (X), 2 (Y) and 3 (Z)"
+            },
+            p
+        ))),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn check_pauli_ids(
+    name: &str,
+    target_indices: &[usize],
+    pauli_ids: &[u8],
+    min_id: u8,
+) -> PyResult<()> {
+    if pauli_ids.len() != target_indices.len() {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "{} gate requires one Pauli id per target qubit: {} ids for {} qubits",
+            name,
+            pauli_ids.len(),
+            target_indices.len()
+        )));
+    }
+    check_pauli_id_range(name, pauli_ids, min_id)
+}
+
+pub(crate) fn check_pauli_rotation(
+    name: &str,
+    target_indices: &[usize],
+    pauli_ids: &[u8],
+) -> PyResult<()> {
+    check_distinct_indices(name, target_indices)?;
+    check_pauli_ids(name, target_indices, pauli_ids, 1)
+}
+
 #[pyfunction(
     name = "Identity",
     signature = (target_index),
@@ -148,21 +208,51 @@ pub fn rz<'py>(target_index: usize, angle: f64) -> QuantumGate {
     QuantumGate::RZ(target_index, angle)
 }
 
+#[pyfunction(
+    name = "RX_deg",
+    signature = (target_index, degrees),
+    text_signature = "(target_index: int, degrees: float)",
+)]
+pub fn rx_deg(target_index: usize, degrees: f64) -> QuantumGate {
+    QuantumGate::RX(target_index, degrees.to_radians())
+}
+
+#[pyfunction(
+    name = "RY_deg",
+    signature = (target_index, degrees),
+    text_signature = "(target_index: int, degrees: float)",
+)]
+pub fn ry_deg(target_index: usize, degrees: f64) -> QuantumGate {
+    QuantumGate::RY(target_index, degrees.to_radians())
+}
+
+#[pyfunction(
+    name = "RZ_deg",
+    signature = (target_index, degrees),
+    text_signature = "(target_index: int, degrees: float)",
+)]
+pub fn rz_deg(target_index: usize, degrees: f64) -> QuantumGate {
+    QuantumGate::RZ(target_index, degrees.to_radians())
+}
+
 #[pyfunction(
     name = "ParametricRX",
     signature = (target_index),
     text_signature = "(target_index: int)",
 )]
 pub fn parametric_rx(target_index: usize) -> ParametricQuantumGate {
-    ParametricQuantumGate(GenericGateProperty {
-        name: "ParametricRX".to_owned().into(),
-        target_indices: vec![target_index].into(),
-        control_indices: vec![].into(),
-        classical_indices: vec![].into(),
-        params: vec![].into(),
-        pauli_ids: vec![].into(),
-        unitary_matrix: None,
-    })
+    ParametricQuantumGate(
+        GenericGateProperty {
+            name: "ParametricRX".to_owned().into(),
+            target_indices: vec![target_index].into(),
+            control_indices: vec![].into(),
+            classical_indices: vec![].into(),
+            params: vec![].into(),
+            pauli_ids: vec![].into(),
+            unitary_matrix: None,
+        },
+        None,
+    )
 }
 
 #[pyfunction(
@@ -171,15 +261,18 @@ pub fn parametric_rx(target_index: usize) -> ParametricQuantumGate {
     text_signature = "(target_index: int)",
 )]
 pub fn parametric_ry(target_index: usize) -> ParametricQuantumGate {
-    ParametricQuantumGate(GenericGateProperty {
-        name: "ParametricRY".to_owned().into(),
-        target_indices: vec![target_index].into(),
-        control_indices: vec![].into(),
-        classical_indices: vec![].into(),
-        params: vec![].into(),
-        pauli_ids: vec![].into(),
-        unitary_matrix: None,
-    })
+    ParametricQuantumGate(
+        GenericGateProperty {
+            name: "ParametricRY".to_owned().into(),
+            target_indices: vec![target_index].into(),
+            control_indices: vec![].into(),
+            classical_indices: vec![].into(),
+            params: vec![].into(),
+            pauli_ids: vec![].into(),
+            unitary_matrix: None,
+        },
+        None,
+    )
 }
 
 #[pyfunction(
@@ -188,15 +281,18 @@ pub fn parametric_ry(target_index: usize) -> ParametricQuantumGate {
     text_signature = "(target_index: int)",
 )]
 pub fn parametric_rz(target_index: usize) -> ParametricQuantumGate {
-    ParametricQuantumGate(GenericGateProperty {
-        name: "ParametricRZ".to_owned().into(),
-        target_indices: vec![target_index].into(),
-        control_indices: vec![].into(),
-        classical_indices: vec![].into(),
-        params: vec![].into(),
-        pauli_ids: vec![].into(),
-        unitary_matrix: None,
-    })
+    ParametricQuantumGate(
+        GenericGateProperty {
+            name: "ParametricRZ".to_owned().into(),
+            target_indices: vec![target_index].into(),
+            control_indices: vec![].into(),
+            classical_indices: vec![].into(),
+            params: vec![].into(),
+            pauli_ids: vec![].into(),
+            unitary_matrix: None,
+        },
+        None,
+    )
 }
 
 #[pyfunction(
@@ -207,16 +303,20 @@ pub fn parametric_rz(target_index: usize) -> ParametricQuantumGate {
 pub fn parametric_pauli_rotation(
     target_indices: Vec<usize>,
     pauli_ids: Vec<u8>,
-) -> ParametricQuantumGate {
-    ParametricQuantumGate(GenericGateProperty {
-        name: "ParametricPauliRotation".to_owned().into(),
-        target_indices: target_indices.into(),
-        control_indices: vec![].into(),
-        classical_indices: vec![].into(),
-        params: vec![].into(),
-        pauli_ids: pauli_ids.into(),
-        unitary_matrix: None,
-    })
+) -> PyResult<ParametricQuantumGate> {
+    check_pauli_rotation("ParametricPauliRotation", &target_indices, &pauli_ids)?;
+    Ok(ParametricQuantumGate(
+        GenericGateProperty {
+            name: "ParametricPauliRotation".to_owned().into(),
+            target_indices: target_indices.into(),
+            control_indices: vec![].into(),
+            classical_indices: vec![].into(),
+            params: vec![].into(),
+            pauli_ids: pauli_ids.into(),
+            unitary_matrix: None,
+        },
+        None,
+    ))
 }
 
 #[pyfunction(
@@ -246,13 +346,23 @@ pub fn u3<'py>(target_index: usize, theta: f64, phi: f64, lmd: f64) -> QuantumGa
     QuantumGate::U3(target_index, theta, phi, lmd)
 }
 
+#[pyfunction(
+    name = "GlobalPhase",
+    signature = (target_index, phase),
+    text_signature = "(target_index: int, phase: float)",
+)]
+pub fn global_phase(target_index: usize, phase: f64) -> QuantumGate {
+    QuantumGate::GlobalPhase(target_index, phase)
+}
+
 #[pyfunction(
     name = "CNOT",
     signature = (control_index, target_index),
     text_signature = "(control_index: int, target_index: int)",
 )]
-pub fn cnot(control_index: usize, target_index: usize) -> QuantumGate {
-    QuantumGate::CNOT(control_index, target_index)
+pub fn cnot(control_index: usize, target_index: usize) -> PyResult<QuantumGate> {
+    check_distinct_indices("CNOT", &[control_index, target_index])?;
+    Ok(QuantumGate::CNOT(control_index, target_index))
 }
 
 #[pyfunction(
@@ -260,8 +370,39 @@ pub fn cnot(control_index: usize, target_index: usize) -> QuantumGate {
     signature = (control_index, target_index),
     text_signature = "(control_index: int, target_index: int)",
 )]
-pub fn cz(control_index: usize, target_index: usize) -> QuantumGate {
-    QuantumGate::CZ(control_index, target_index)
+pub fn cz(control_index: usize, target_index: usize) -> PyResult<QuantumGate> {
+    check_distinct_indices("CZ", &[control_index, target_index])?;
+    Ok(QuantumGate::CZ(control_index, target_index))
+}
+
+#[pyfunction(
+    name = "CRX",
+    signature = (control_index, target_index, angle),
+    text_signature = "(control_index: int, target_index: int, angle: float)",
+)]
+pub fn crx(control_index: usize, target_index: usize, angle: f64) -> PyResult<QuantumGate> {
+    check_distinct_indices("CRX", &[control_index, target_index])?;
+    Ok(QuantumGate::CRX(control_index, target_index, angle))
+}
+
+#[pyfunction(
+    name = "CRY",
+    signature = (control_index, target_index, angle),
+    text_signature = "(control_index: int, target_index: int, angle: float)",
+)]
+pub fn cry(control_index: usize, target_index: usize, angle: f64) -> PyResult<QuantumGate> {
+    check_distinct_indices("CRY", &[control_index, target_index])?;
+    Ok(QuantumGate::CRY(control_index, target_index, angle))
+}
+
+#[pyfunction(
+    name = "CRZ",
+    signature = (control_index, target_index, angle),
+    text_signature = "(control_index: int, target_index: int, angle: float)",
+)]
+pub fn crz(control_index: usize, target_index: usize, angle: f64) -> PyResult<QuantumGate> {
+    check_distinct_indices("CRZ", &[control_index, target_index])?;
+    Ok(QuantumGate::CRZ(control_index, target_index, angle))
 }
 
 #[pyfunction(
@@ -269,8 +410,39 @@ pub fn cz(control_index: usize, target_index: usize) -> QuantumGate {
     signature = (target_index1, target_index2),
     text_signature = "(target_index1: int, target_index2: int)",
 )]
-pub fn swap(target_index1: usize, target_index2: usize) -> QuantumGate {
-    QuantumGate::SWAP(target_index1, target_index2)
+pub fn swap(target_index1: usize, target_index2: usize) -> PyResult<QuantumGate> {
+    check_distinct_indices("SWAP", &[target_index1, target_index2])?;
+    Ok(QuantumGate::SWAP(target_index1, target_index2))
+}
+
+#[pyfunction(
+    name = "ISWAP",
+    signature = (target_index1, target_index2),
+    text_signature = "(target_index1: int, target_index2: int)",
+)]
+pub fn iswap(target_index1: usize, target_index2: usize) -> PyResult<QuantumGate> {
+    check_distinct_indices("ISWAP", &[target_index1, target_index2])?;
+    Ok(QuantumGate::ISWAP(target_index1, target_index2))
+}
+
+#[pyfunction(
+    name = "SqrtISWAP",
+    signature = (target_index1, target_index2),
+    text_signature = "(target_index1: int, target_index2: int)",
+)]
+pub fn sqrt_iswap(target_index1: usize, target_index2: usize) -> PyResult<QuantumGate> {
+    check_distinct_indices("SqrtISWAP", &[target_index1, target_index2])?;
+    Ok(QuantumGate::SqrtISWAP(target_index1, target_index2))
+}
+
+#[pyfunction(
+    name = "ECR",
+    signature = (target_index1, target_index2),
+    text_signature = "(target_index1: int, target_index2: int)",
+)]
+pub fn ecr(target_index1: usize, target_index2: usize) -> PyResult<QuantumGate> {
+    check_distinct_indices("ECR", &[target_index1, target_index2])?;
+    Ok(QuantumGate::ECR(target_index1, target_index2))
 }
 
 #[pyfunction(
@@ -278,8 +450,17 @@ pub fn swap(target_index1: usize, target_index2: usize) -> QuantumGate {
     signature = (control_index1, control_index2, target_index),
     text_signature = "(control_index1: int, control_index2: int, target_index: int)",
 )]
-pub fn toffoli(control_index1: usize, control_index2: usize, target_index: usize) -> QuantumGate {
-    QuantumGate::TOFFOLI(control_index1, control_index2, target_index)
+pub fn toffoli(
+    control_index1: usize,
+    control_index2: usize,
+    target_index: usize,
+) -> PyResult<QuantumGate> {
+    check_distinct_indices("TOFFOLI", &[control_index1, control_index2, target_index])?;
+    Ok(QuantumGate::TOFFOLI(
+        control_index1,
+        control_index2,
+        target_index,
+    ))
 }
 
 #[pyfunction(
@@ -293,32 +474,48 @@ pub fn unitary_matrix(
 ) -> PyResult<QuantumGate> {
     let dim = 2usize.pow(target_indices.len() as u32);
     if unitary_matrix.len() == dim && unitary_matrix.iter().all(|v| v.len() == dim) {
-        if (0..dim).all(|i| {
-            (0..dim).all(|j| {
-                ((0..dim)
-                    .map(|k| unitary_matrix[i][k] * unitary_matrix[j][k].conj())
-                    .sum::<Complex64>()
-                    - Complex64::new(Into::<u8>::into(i == j) as f64, 0f64))
-                .abs()
-                    < 1e-5
-            })
-        }) {
+        if crate::circuit::gate::matrix::is_unitary(&unitary_matrix, 1e-5) {
             Ok(QuantumGate::UnitaryMatrix(
                 target_indices.into(),
                 unitary_matrix.into_iter().map(Into::into).collect(),
             ))
         } else {
-            Err(pyo3::exceptions::PyValueError::new_err(
+            Err(InvalidUnitaryError::new_err(
                 "The given matrix is not unitary.",
             ))
         }
     } else {
-        Err(pyo3::exceptions::PyValueError::new_err(
+        Err(InvalidUnitaryError::new_err(
             "The number of qubits does not match the size of the unitary matrix.",
         ))
     }
 }
 
+/// UnitaryMatrix gate from the matrix entries in row-major order.
+pub fn unitary_matrix_from_buffer(
+    target_indices: Vec<usize>,
+    flat: Vec<Complex64>,
+    dim: usize,
+) -> PyResult<QuantumGate> {
+    if dim.checked_mul(dim) != Some(flat.len()) {
+        return Err(InvalidUnitaryError::new_err(format!(
+            "The buffer has {} entries but a {}x{} matrix was requested.",
+            flat.len(),
+            dim,
+            dim
+        )));
+    }
+    if 1usize.checked_shl(target_indices.len() as u32) != Some(dim) {
+        return Err(InvalidUnitaryError::new_err(
+            "The number of qubits does not match the size of the unitary matrix.",
+        ));
+    }
+    unitary_matrix(
+        target_indices,
+        flat.chunks(dim).map(|row| row.to_vec()).collect(),
+    )
+}
+
 #[pyfunction(
     name = "SingleQubitUnitaryMatrix",
     signature = (target_index, mat),
@@ -349,8 +546,10 @@ pub fn two_qubit_unitary_matrix(
     signature = (target_indices, pauli_ids),
     text_signature = "(target_indices: Sequence[int], pauli_ids: Sequence[int])",
 )]
-pub fn pauli(target_indices: Vec<usize>, pauli_ids: Vec<u8>) -> QuantumGate {
-    QuantumGate::Pauli(target_indices.into(), pauli_ids.into())
+pub fn pauli(target_indices: Vec<usize>, pauli_ids: Vec<u8>) -> PyResult<QuantumGate> {
+    check_distinct_indices("Pauli", &target_indices)?;
+    check_pauli_ids("Pauli", &target_indices, &pauli_ids, 0)?;
+    Ok(QuantumGate::Pauli(target_indices.into(), pauli_ids.into()))
 }
 
 #[pyfunction(
@@ -358,8 +557,17 @@ pub fn pauli(target_indices: Vec<usize>, pauli_ids: Vec<u8>) -> QuantumGate {
     signature = (target_indices, pauli_ids, angle),
     text_signature = "(target_indices: Sequence[int], pauli_ids: Sequence[int], angle: float)",
 )]
-pub fn pauli_rotation(target_indices: Vec<usize>, pauli_ids: Vec<u8>, angle: f64) -> QuantumGate {
-    QuantumGate::PauliRotation(target_indices.into(), pauli_ids.into(), angle)
+pub fn pauli_rotation(
+    target_indices: Vec<usize>,
+    pauli_ids: Vec<u8>,
+    angle: f64,
+) -> PyResult<QuantumGate> {
+    check_pauli_rotation("PauliRotation", &target_indices, &pauli_ids)?;
+    Ok(QuantumGate::PauliRotation(
+        target_indices.into(),
+        pauli_ids.into(),
+        angle,
+    ))
 }
 
 #[pyfunction(
@@ -401,6 +609,9 @@ pub fn py_module<'py>(py: Python<'py>) -> PyResult<Bound<'py, PyModule>> {
     m.add_wrapped(wrap_pyfunction!(rx))?;
     m.add_wrapped(wrap_pyfunction!(ry))?;
     m.add_wrapped(wrap_pyfunction!(rz))?;
+    m.add_wrapped(wrap_pyfunction!(rx_deg))?;
+    m.add_wrapped(wrap_pyfunction!(ry_deg))?;
+    m.add_wrapped(wrap_pyfunction!(rz_deg))?;
     m.add_wrapped(wrap_pyfunction!(parametric_rx))?;
     m.add_wrapped(wrap_pyfunction!(parametric_ry))?;
     m.add_wrapped(wrap_pyfunction!(parametric_rz))?;
@@ -408,9 +619,16 @@ pub fn py_module<'py>(py: Python<'py>) -> PyResult<Bound<'py, PyModule>> {
     m.add_wrapped(wrap_pyfunction!(u1))?;
     m.add_wrapped(wrap_pyfunction!(u2))?;
     m.add_wrapped(wrap_pyfunction!(u3))?;
+    m.add_wrapped(wrap_pyfunction!(global_phase))?;
     m.add_wrapped(wrap_pyfunction!(cnot))?;
     m.add_wrapped(wrap_pyfunction!(cz))?;
+    m.add_wrapped(wrap_pyfunction!(crx))?;
+    m.add_wrapped(wrap_pyfunction!(cry))?;
+    m.add_wrapped(wrap_pyfunction!(crz))?;
     m.add_wrapped(wrap_pyfunction!(swap))?;
+    m.add_wrapped(wrap_pyfunction!(iswap))?;
+    m.add_wrapped(wrap_pyfunction!(sqrt_iswap))?;
+    m.add_wrapped(wrap_pyfunction!(ecr))?;
     m.add_wrapped(wrap_pyfunction!(toffoli))?;
     m.add_wrapped(wrap_pyfunction!(unitary_matrix))?;
     m.add_wrapped(wrap_pyfunction!(single_qubit_unitary_matrix))?;
@@ -439,10 +657,16 @@ impl QuantumGate<MaybeUnbound> {
             Self::SqrtYdag(q) => Ok(Ok(sqrtydag(q))),
             Self::T(q) => Ok(Ok(t(q))),
             Self::Tdag(q) => Ok(Ok(tdag(q))),
-            Self::CNOT(q0, q1) => Ok(Ok(cnot(q0, q1))),
-            Self::CZ(q0, q1) => Ok(Ok(cz(q0, q1))),
-            Self::SWAP(q0, q1) => Ok(Ok(swap(q0, q1))),
-            Self::TOFFOLI(q0, q1, q2) => Ok(Ok(toffoli(q0, q1, q2))),
+            Self::CNOT(q0, q1) => Ok(Ok(cnot(q0, q1)?)),
+            Self::CZ(q0, q1) => Ok(Ok(cz(q0, q1)?)),
+            Self::CRX(q0, q1, p) => Ok(Ok(crx(q0, q1, p)?)),
+            Self::CRY(q0, q1, p) => Ok(Ok(cry(q0, q1, p)?)),
+            Self::CRZ(q0, q1, p) => Ok(Ok(crz(q0, q1, p)?)),
+            Self::SWAP(q0, q1) => Ok(Ok(swap(q0, q1)?)),
+            Self::ISWAP(q0, q1) => Ok(Ok(iswap(q0, q1)?)),
+            Self::SqrtISWAP(q0, q1) => Ok(Ok(sqrt_iswap(q0, q1)?)),
+            Self::ECR(q0, q1) => Ok(Ok(ecr(q0, q1)?)),
+            Self::TOFFOLI(q0, q1, q2) => Ok(Ok(toffoli(q0, q1, q2)?)),
             Self::UnitaryMatrix(qs, mat) => Ok(Ok(unitary_matrix(
                 qs.into(),
                 mat.into_iter().map(Into::into).collect(),
@@ -462,11 +686,12 @@ impl QuantumGate<MaybeUnbound> {
             Self::U1(q, p) => Ok(Ok(u1(q, p))),
             Self::U2(q, p0, p1) => Ok(Ok(u2(q, p0, p1))),
             Self::U3(q, p0, p1, p2) => Ok(Ok(u3(q, p0, p1, p2))),
-            Self::Pauli(qs, ps) => Ok(Ok(pauli(qs.into(), ps.into()))),
+            Self::GlobalPhase(q, p) => Ok(Ok(global_phase(q, p))),
+            Self::Pauli(qs, ps) => Ok(Ok(pauli(qs.into(), ps.into())?)),
             Self::PauliRotation(qs, ps, a) => match a {
-                MaybeUnbound::Bound(a) => Ok(Ok(pauli_rotation(qs.into(), ps.into(), a))),
+                MaybeUnbound::Bound(a) => Ok(Ok(pauli_rotation(qs.into(), ps.into(), a)?)),
                 MaybeUnbound::Unbound(pid) => {
-                    Ok(Err((parametric_pauli_rotation(qs.into(), ps.into()), pid)))
+                    Ok(Err((parametric_pauli_rotation(qs.into(), ps.into())?, pid)))
                 }
             },
             Self::Measurement(qs, cs) => Ok(Ok(measurement(qs.into(), cs.into())?)),
@@ -62,6 +62,12 @@ pub fn convert_add_gate<'py>(
         QuantumGate::U1(q1, p1) => {
             qulacs_circuit.call_method1("add_U1_gate", (*q1, *p1))?;
         }
+        QuantumGate::GlobalPhase(q1, _) => {
+            qulacs_circuit.call_method1(
+                "add_dense_matrix_gate",
+                (vec![*q1], gate.try_matrix().unwrap()),
+            )?;
+        }
         QuantumGate::U2(q1, p1, p2) => {
             qulacs_circuit.call_method1("add_U2_gate", (*q1, *p1, *p2))?;
         }
@@ -77,6 +83,17 @@ pub fn convert_add_gate<'py>(
         QuantumGate::SWAP(q1, q2) => {
             qulacs_circuit.call_method1("add_SWAP_gate", (*q1, *q2))?;
         }
+        QuantumGate::ISWAP(q1, q2)
+        | QuantumGate::SqrtISWAP(q1, q2)
+        | QuantumGate::ECR(q1, q2)
+        | QuantumGate::CRX(q1, q2, _)
+        | QuantumGate::CRY(q1, q2, _)
+        | QuantumGate::CRZ(q1, q2, _) => {
+            qulacs_circuit.call_method1(
+                "add_dense_matrix_gate",
+                (vec![*q1, *q2], gate.try_matrix().unwrap()),
+            )?;
+        }
         QuantumGate::TOFFOLI(q1, q2, q3) => {
             let toffoli_gate = py
                 .import_bound("qulacs.gate")?